@@ -0,0 +1,54 @@
+// Compares the cell-based and segment-based wire engines on the day 3 input.
+//
+// Run with `cargo bench --bench wires`.
+
+extern crate adv_2019;
+
+use adv_2019::wires::segments::{self, SegmentWire};
+use adv_2019::wires::Cursor;
+use std::fs;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: u32 = 20;
+
+/// Runs `f` `RUNS` times after one warmup run, returns the fastest run.
+fn time<T, F: FnMut() -> T>(mut f: F) -> Duration {
+    black_box(f());
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let contents = fs::read_to_string("inputs/day3_1.txt").unwrap();
+    let mut lines = contents.lines();
+    let first = lines.next().unwrap();
+    let second = lines.next().unwrap();
+
+    let cells = time(|| {
+        let mut c = Cursor::new();
+        c.go_string(first, false);
+        c.go_string(second, true);
+        (
+            c.smol_manh_distance_collision(),
+            c.least_steps_for_collision(),
+        )
+    });
+    let segs = time(|| {
+        let a = SegmentWire::from_string(first);
+        let b = SegmentWire::from_string(second);
+        (
+            segments::smol_manh_distance_collision(&a, &b),
+            segments::least_steps_for_collision(&a, &b),
+        )
+    });
+
+    println!("cells:    {:>10.3?}", cells);
+    println!("segments: {:>10.3?}", segs);
+}
@@ -0,0 +1,454 @@
+//! Asteroid fields from day 10: which asteroids can see each other, and the order a rotating
+//! laser vaporizes them in.
+//!
+//! Two asteroids see each other unless a third lies exactly on the line between them. Reducing
+//! each offset from an origin by the gcd of its coordinates gives its direction; asteroids in
+//! the same direction hide each other, so an origin sees one asteroid per distinct direction.
+
+use crate::error::{Error, Result};
+use crate::geom::Point;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub mod render;
+
+pub type Pt = Point<i64>;
+
+/// Greatest common divisor, always non-negative. gcd(0, 0) is 0.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The smallest offset pointing the same way as `delta`. Zero stays zero.
+pub fn direction(delta: Pt) -> Pt {
+    match gcd(delta.x, delta.y) {
+        0 => delta,
+        g => Pt::new(delta.x / g, delta.y / g),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// As seen on the map, where y grows downwards: up, then right.
+    Clockwise,
+    Counterclockwise,
+}
+
+/// Where the vaporizing laser starts pointing, and which way it turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Laser {
+    /// Any non-zero offset.
+    pub start: Pt,
+    pub rotation: Rotation,
+}
+
+impl Default for Laser {
+    /// Up and clockwise, as in the puzzle.
+    fn default() -> Self {
+        Laser {
+            start: Pt::new(0, -1),
+            rotation: Rotation::Clockwise,
+        }
+    }
+}
+
+impl Laser {
+    /// Orders directions by how far the laser turns from its start to reach them. The start
+    /// direction itself comes first.
+    fn cmp(&self, a: Pt, b: Pt) -> Ordering {
+        // Positive cross products turn clockwise on the map.
+        let sign = match self.rotation {
+            Rotation::Clockwise => 1,
+            Rotation::Counterclockwise => -1,
+        };
+        let cross = |u: Pt, v: Pt| sign * (u.x * v.y - u.y * v.x);
+        let dot = |u: Pt, v: Pt| u.x * v.x + u.y * v.y;
+        // 0 for directions less than half a turn from the start, 1 for the rest.
+        let half = |d: Pt| {
+            let c = cross(self.start, d);
+            if c > 0 || (c == 0 && dot(self.start, d) > 0) {
+                0
+            } else {
+                1
+            }
+        };
+        half(a).cmp(&half(b)).then_with(|| 0.cmp(&cross(a, b)))
+    }
+}
+
+/// An asteroid hit by the laser, and on which turn, counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vaporized {
+    pub pos: Pt,
+    pub rotation: usize,
+}
+
+/// Asteroids in the order a laser vaporizes them, see `Field::vaporize`.
+pub struct Vaporization {
+    origin: Pt,
+    /// Asteroids in each direction, nearest first, in the order the laser passes them. Only
+    /// directions with asteroids left at the start of the current turn.
+    queues: Vec<VecDeque<Pt>>,
+    next: usize,
+    rotation: usize,
+}
+
+impl Iterator for Vaporization {
+    type Item = Vaporized;
+
+    fn next(&mut self) -> Option<Vaporized> {
+        let pos = self.queues.get_mut(self.next)?.pop_front()?;
+        let hit = Vaporized {
+            pos: pos + self.origin,
+            rotation: self.rotation,
+        };
+        self.next += 1;
+        if self.next == self.queues.len() {
+            self.queues.retain(|q| !q.is_empty());
+            self.next = 0;
+            self.rotation += 1;
+        }
+        Some(hit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.queues.iter().map(|q| q.len()).sum();
+        (left, Some(left))
+    }
+}
+
+pub struct Field {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    asteroids: Vec<Pt>,
+    occupied: HashSet<Pt>,
+}
+
+impl Field {
+    /// Parses a map of '#' (asteroid) and '.' (empty). Rows must all have the same width, and
+    /// there must be at least one asteroid.
+    pub fn parse(input: &str) -> Result<Field> {
+        let mut width = None;
+        let mut asteroids = Vec::new();
+        let mut height = 0;
+        for (n, line) in input.lines().enumerate() {
+            if let Some(c) = line.chars().find(|c| *c != '#' && *c != '.') {
+                return Err(Error::at_line(n + 1, format!("invalid cell {:?}", c)));
+            }
+            let len = line.chars().count();
+            if *width.get_or_insert(len) != len {
+                return Err(Error::at_line(n + 1, "rows have different widths"));
+            }
+            asteroids.extend(
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(|(x, _)| Pt::new(x as i64, n as i64)),
+            );
+            height += 1;
+        }
+        let width = width.unwrap_or(0);
+        if width == 0 {
+            return Err(Error::parse("field is empty"));
+        }
+        if asteroids.is_empty() {
+            return Err(Error::parse("field has no asteroids"));
+        }
+        Ok(Field {
+            width,
+            height,
+            occupied: asteroids.iter().copied().collect(),
+            asteroids,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Positions of all asteroids, row by row.
+    pub fn asteroids(&self) -> &[Pt] {
+        &self.asteroids
+    }
+
+    pub fn has(&self, pt: &Pt) -> bool {
+        self.occupied.contains(pt)
+    }
+
+    /// Number of other asteroids visible from an asteroid at `origin`, 0 if there is none.
+    pub fn count_visible_asteroids(&self, origin: &Pt) -> usize {
+        if !self.has(origin) {
+            return 0;
+        }
+        self.asteroids
+            .iter()
+            .filter(|pt| *pt != origin)
+            .map(|pt| direction(*pt - *origin))
+            .collect::<HashSet<Pt>>()
+            .len()
+    }
+
+    /// The asteroid that sees the most others, and how many. Ties go to the first, row by row.
+    pub fn find_best_position(&self) -> (usize, Pt) {
+        let mut best = (0, self.asteroids[0]);
+        for origin in self.asteroids.iter() {
+            let count = self.count_visible_asteroids(origin);
+            if count > best.0 {
+                best = (count, *origin);
+            }
+        }
+        best
+    }
+
+    /// Every other asteroid, in the order a laser at `origin` vaporizes them. It hits the
+    /// nearest asteroid in each direction it passes, so those behind wait for a later turn.
+    /// Panics if the laser's start is zero.
+    pub fn vaporize(&self, origin: &Pt, laser: Laser) -> Vaporization {
+        assert!(laser.start != Pt::origin(), "laser has no direction");
+        let mut by_direction: HashMap<Pt, Vec<Pt>> = HashMap::new();
+        for pt in self.asteroids.iter().filter(|pt| *pt != origin) {
+            let delta = *pt - *origin;
+            by_direction
+                .entry(direction(delta))
+                .or_default()
+                .push(delta);
+        }
+        let mut directions: Vec<Pt> = by_direction.keys().copied().collect();
+        directions.sort_by(|a, b| laser.cmp(*a, *b));
+        let queues = directions
+            .iter()
+            .map(|d| {
+                let mut line = by_direction.remove(d).unwrap();
+                line.sort_by_key(|p| p.x.abs() + p.y.abs());
+                VecDeque::from(line)
+            })
+            .collect();
+        Vaporization {
+            origin: *origin,
+            queues,
+            next: 0,
+            rotation: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(direction(Pt::new(2, 14)), Pt::new(1, 7));
+        assert_eq!(direction(Pt::new(4, 0)), Pt::new(1, 0));
+        assert_eq!(direction(Pt::new(0, -2)), Pt::new(0, -1));
+        assert_eq!(direction(Pt::new(-6, 9)), Pt::new(-2, 3));
+        assert_eq!(direction(Pt::new(0, 0)), Pt::new(0, 0));
+    }
+
+    #[test]
+    fn test_laser_order() {
+        let laser = Laser::default();
+        let mut dirs = vec![
+            Pt::new(-1, -1),
+            Pt::new(0, 1),
+            Pt::new(2, -1),
+            Pt::new(-1, 0),
+            Pt::new(1, 0),
+            Pt::new(0, -1),
+            Pt::new(1, -2),
+            Pt::new(-1, 3),
+            Pt::new(1, 1),
+        ];
+        dirs.sort_by(|a, b| laser.cmp(*a, *b));
+        let clockwise = vec![
+            Pt::new(0, -1),
+            Pt::new(1, -2),
+            Pt::new(2, -1),
+            Pt::new(1, 0),
+            Pt::new(1, 1),
+            Pt::new(0, 1),
+            Pt::new(-1, 3),
+            Pt::new(-1, 0),
+            Pt::new(-1, -1),
+        ];
+        assert_eq!(dirs, clockwise);
+
+        // Starting left and turning the other way.
+        let laser = Laser {
+            start: Pt::new(-3, 0),
+            rotation: Rotation::Counterclockwise,
+        };
+        dirs.sort_by(|a, b| laser.cmp(*a, *b));
+        assert_eq!(
+            dirs,
+            vec![
+                Pt::new(-1, 0),
+                Pt::new(-1, 3),
+                Pt::new(0, 1),
+                Pt::new(1, 1),
+                Pt::new(1, 0),
+                Pt::new(2, -1),
+                Pt::new(1, -2),
+                Pt::new(0, -1),
+                Pt::new(-1, -1),
+            ]
+        );
+    }
+
+    /// Smallest step along `p`, by trial division rather than `gcd`.
+    fn simplify(p: &Pt) -> Pt {
+        for div in (2..=std::cmp::max(p.x.abs(), p.y.abs())).rev() {
+            if (p.x % div == 0) && (p.y % div) == 0 {
+                return Pt::new(p.x / div, p.y / div);
+            }
+        }
+        *p
+    }
+
+    /// The original check: walk every cell between the two asteroids looking for another.
+    fn occluded_count(field: &Field, origin: &Pt) -> usize {
+        if !field.has(origin) {
+            return 0;
+        }
+        let visible = |to: &Pt| {
+            let delta = simplify(&(*origin - *to));
+            let mut pt = *to + delta;
+            while pt != *origin {
+                if field.has(&pt) {
+                    return false;
+                }
+                pt += delta;
+            }
+            true
+        };
+        field
+            .asteroids()
+            .iter()
+            .filter(|to| *to != origin && visible(to))
+            .count()
+    }
+
+    #[test]
+    fn test_matches_occlusion() {
+        let files = [
+            "inputs/day10.txt",
+            "inputs/day10_test.txt",
+            "inputs/day10_test2.txt",
+            "inputs/day10_test_mini.txt",
+        ];
+        for file in files.iter() {
+            let field = Field::parse(&std::fs::read_to_string(file).unwrap()).unwrap();
+            // Empty cells too, which see nothing.
+            for y in 0..field.height() as i64 {
+                for x in 0..field.width() as i64 {
+                    let origin = Pt::new(x, y);
+                    assert_eq!(
+                        field.count_visible_asteroids(&origin),
+                        occluded_count(&field, &origin),
+                        "{} from {:?}",
+                        file,
+                        origin
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_best_position() {
+        let best = |text: &str| Field::parse(text).unwrap().find_best_position();
+        assert_eq!(
+            best(".#..#\n.....\n#####\n....#\n...##\n"),
+            (8, Pt::new(3, 4))
+        );
+        let test2 = std::fs::read_to_string("inputs/day10_test2.txt").unwrap();
+        assert_eq!(best(&test2), (210, Pt::new(11, 13)));
+        assert_eq!(best("#"), (0, Pt::new(0, 0)));
+        assert_eq!(
+            Field::parse("...\n...").err().unwrap().to_string(),
+            "field has no asteroids"
+        );
+        assert!(Field::parse("").is_err());
+        assert_eq!(
+            Field::parse("#.\n#").err().unwrap().to_string(),
+            "line 2: rows have different widths"
+        );
+    }
+
+    #[test]
+    fn test_vaporize() {
+        let field =
+            Field::parse(&std::fs::read_to_string("inputs/day10_test2.txt").unwrap()).unwrap();
+        let origin = Pt::new(11, 13);
+        let order: Vec<Vaporized> = field.vaporize(&origin, Laser::default()).collect();
+        assert_eq!(order.len(), field.asteroids().len() - 1);
+        let nth = |n: usize| order[n - 1].pos;
+        assert_eq!(nth(1), Pt::new(11, 12));
+        assert_eq!(nth(2), Pt::new(12, 1));
+        assert_eq!(nth(3), Pt::new(12, 2));
+        assert_eq!(nth(10), Pt::new(12, 8));
+        assert_eq!(nth(20), Pt::new(16, 0));
+        assert_eq!(nth(50), Pt::new(16, 9));
+        assert_eq!(nth(100), Pt::new(10, 16));
+        assert_eq!(nth(199), Pt::new(9, 6));
+        assert_eq!(nth(200), Pt::new(8, 2));
+        assert_eq!(nth(201), Pt::new(10, 9));
+        assert_eq!(nth(299), Pt::new(11, 1));
+        // Each turn hits one asteroid per direction that has any left.
+        assert_eq!(order[0].rotation, 1);
+        assert_eq!(order[209].rotation, 1);
+        assert_eq!(order[210].rotation, 2);
+        // The last turn is the one that clears the most crowded direction.
+        let mut crowded: HashMap<Pt, usize> = HashMap::new();
+        for pt in field.asteroids().iter().filter(|pt| **pt != origin) {
+            *crowded.entry(direction(*pt - origin)).or_default() += 1;
+        }
+        assert_eq!(order.last().unwrap().rotation, 12);
+        assert_eq!(crowded.values().max(), Some(&12));
+        assert!(order.windows(2).all(|w| w[0].rotation <= w[1].rotation));
+        let mut vaporization = field.vaporize(&origin, Laser::default());
+        assert_eq!(vaporization.size_hint(), (299, Some(299)));
+        assert_eq!(vaporization.nth(199).map(|v| v.pos), Some(Pt::new(8, 2)));
+    }
+
+    #[test]
+    fn test_vaporize_mirrored() {
+        // Turning counterclockwise over a mirrored field hits the mirrored asteroids in order.
+        let text = std::fs::read_to_string("inputs/day10_test2.txt").unwrap();
+        let mirrored: String = text
+            .lines()
+            .map(|l| l.chars().rev().collect::<String>() + "\n")
+            .collect();
+        let field = Field::parse(&text).unwrap();
+        let mirror = Field::parse(&mirrored).unwrap();
+        let flip = |p: Pt| Pt::new(field.width() as i64 - 1 - p.x, p.y);
+        let ccw = Laser {
+            start: Pt::new(0, -1),
+            rotation: Rotation::Counterclockwise,
+        };
+        let origin = Pt::new(11, 13);
+        let order: Vec<Vaporized> = field.vaporize(&origin, Laser::default()).collect();
+        let mirrored: Vec<Vaporized> = mirror
+            .vaporize(&flip(origin), ccw)
+            .map(|v| Vaporized {
+                pos: flip(v.pos),
+                ..v
+            })
+            .collect();
+        assert_eq!(order, mirrored);
+    }
+}
@@ -0,0 +1,208 @@
+//! Pictures of an asteroid field: a text grid, and an SVG heatmap.
+//!
+//! Each asteroid is labelled with how many others it sees, and the best station is marked, as
+//! is a station chosen instead of it. Given a vaporization order, asteroids are labelled with
+//! their place in it as well.
+
+use super::{Field, Pt, Vaporized};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Size of a cell in the SVG, in pixels.
+const CELL: usize = 32;
+
+/// Visible counts of every asteroid.
+fn counts(field: &Field) -> HashMap<Pt, usize> {
+    field
+        .asteroids()
+        .iter()
+        .map(|pt| (*pt, field.count_visible_asteroids(pt)))
+        .collect()
+}
+
+/// Asteroid with the highest count, the first row by row on ties, as
+/// `Field::find_best_position` picks it.
+fn best(field: &Field, counts: &HashMap<Pt, usize>) -> Pt {
+    *field
+        .asteroids()
+        .iter()
+        .min_by_key(|pt| Reverse(counts[*pt]))
+        .expect("fields have asteroids")
+}
+
+/// Place of each asteroid in the order, counting from 1.
+fn places(order: &[Vaporized]) -> HashMap<Pt, usize> {
+    order
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.pos, i + 1))
+        .collect()
+}
+
+/// One cell per position, lined up: the visible count of each asteroid and '.' for empty
+/// space. With `order`, each count is followed by the asteroid's place in it, as `count/place`,
+/// and 'X' for the asteroid the laser is on. The best station is in brackets, and `station`, if
+/// it's another asteroid, in angle brackets.
+pub fn text(field: &Field, station: Option<Pt>, order: Option<&[Vaporized]>) -> String {
+    let counts = counts(field);
+    let best = best(field, &counts);
+    let places = order.map(places);
+    let labels: HashMap<Pt, String> = counts
+        .into_iter()
+        .map(|(pt, n)| {
+            let label = match &places {
+                Some(places) => match places.get(&pt) {
+                    Some(place) => format!("{}/{}", n, place),
+                    None => format!("{}/X", n),
+                },
+                None => n.to_string(),
+            };
+            (pt, label)
+        })
+        .collect();
+    let width = labels.values().map(|l| l.len()).max().unwrap_or(1);
+    let mut out = String::new();
+    for y in 0..field.height() as i64 {
+        let mut line = String::new();
+        for x in 0..field.width() as i64 {
+            let pt = Pt::new(x, y);
+            let label = labels.get(&pt).map_or(".", |l| l.as_str());
+            let (open, close) = if pt == best {
+                ('[', ']')
+            } else if Some(pt) == station {
+                ('<', '>')
+            } else {
+                (' ', ' ')
+            };
+            line.push_str(&format!("{}{:>w$}{}", open, label, close, w = width));
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Blue for the fewest visible, through green and yellow, to red for the most.
+fn heat(count: usize, min: usize, max: usize) -> String {
+    let hue = 240 - 240 * (count - min) / (max - min).max(1);
+    format!("hsl({}, 80%, 55%)", hue)
+}
+
+/// The field as an SVG heatmap: each asteroid is a circle colored and labelled by its visible
+/// count. The best station has a thick white ring, and `station`, if it's another asteroid, a
+/// dashed one. With an order, each asteroid also gets its place in the order in a corner of its
+/// cell.
+pub fn svg(field: &Field, station: Option<Pt>, order: Option<&[Vaporized]>) -> String {
+    let counts = counts(field);
+    let best = best(field, &counts);
+    let places = order.map(places).unwrap_or_default();
+    let min = counts.values().copied().min().unwrap_or(0);
+    let max = counts.values().copied().max().unwrap_or(0);
+    let (width, height) = (field.width() * CELL, field.height() * CELL);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         font-family=\"sans-serif\" text-anchor=\"middle\">\n\
+         <rect width=\"{}\" height=\"{}\" fill=\"#111\"/>\n",
+        width, height, width, height
+    );
+    let radius = CELL * 2 / 5;
+    for pt in field.asteroids().iter() {
+        let count = counts[pt];
+        let (cx, cy) = (
+            pt.x as usize * CELL + CELL / 2,
+            pt.y as usize * CELL + CELL / 2,
+        );
+        let ring = if *pt == best {
+            " stroke=\"#fff\" stroke-width=\"4\""
+        } else if Some(*pt) == station {
+            " stroke=\"#fff\" stroke-width=\"4\" stroke-dasharray=\"4 3\""
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"{}><title>({}, {}) sees {}</title>\
+             </circle>\n",
+            cx,
+            cy,
+            radius,
+            heat(count, min, max),
+            ring,
+            pt.x,
+            pt.y,
+            count
+        ));
+        out.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"11\">{}</text>\n",
+            cx,
+            cy + 4,
+            count
+        ));
+        if let Some(place) = places.get(pt) {
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" font-size=\"8\" fill=\"#fff\" text-anchor=\"end\">{}\
+                 </text>\n",
+                (pt.x as usize + 1) * CELL - 1,
+                (pt.y as usize + 1) * CELL - 1,
+                place
+            ));
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Laser;
+    use super::*;
+
+    const MINI: &str = ".#..#\n.....\n#####\n....#\n...##\n";
+
+    #[test]
+    fn test_text() {
+        let field = Field::parse(MINI).unwrap();
+        assert_eq!(
+            text(&field, None, None),
+            " .  7  .  .  7\n .  .  .  .  .\n 6  7  7  7  5\n .  .  .  .  7\n .  .  . [8] 7\n"
+        );
+        // The best station is marked whether or not it's asked for.
+        let best = Pt::new(3, 4);
+        assert_eq!(text(&field, None, None), text(&field, Some(best), None));
+        assert_eq!(
+            text(&field, Some(Pt::new(4, 2)), None),
+            " .  7  .  .  7\n .  .  .  .  .\n 6  7  7  7 <5>\n .  .  .  .  7\n .  .  . [8] 7\n"
+        );
+        let order: Vec<Vaporized> = field.vaporize(&best, Laser::default()).collect();
+        assert_eq!(
+            text(&field, Some(best), Some(&order)),
+            "   .  7/9    .    .  7/2\n   .    .    .    .    .\n 6/6  7/7  7/8  7/1  5/3\n   \
+             .    .    .    .  7/4\n   .    .    . [8/X] 7/5\n"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let field = Field::parse(MINI).unwrap();
+        let station = Pt::new(3, 4);
+        let plain = svg(&field, Some(station), None);
+        assert!(plain.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"160\""));
+        assert!(plain.ends_with("</svg>\n"));
+        assert_eq!(plain.matches("<circle").count(), 10);
+        assert_eq!(plain.matches("stroke-width").count(), 1);
+        assert_eq!(plain, svg(&field, None, None));
+        let chosen = svg(&field, Some(Pt::new(4, 2)), None);
+        assert_eq!(chosen.matches("stroke-width").count(), 2);
+        assert_eq!(chosen.matches("stroke-dasharray").count(), 1);
+        // The station sees the most, so it's the reddest, and (4, 2) the fewest.
+        assert!(
+            plain.contains("<circle cx=\"144\" cy=\"80\" r=\"12\" fill=\"hsl(240, 80%, 55%)\">")
+        );
+        assert!(plain.contains(
+            "<circle cx=\"112\" cy=\"144\" r=\"12\" fill=\"hsl(0, 80%, 55%)\" stroke=\"#fff\" \
+             stroke-width=\"4\"><title>(3, 4) sees 8</title></circle>"
+        ));
+        let order: Vec<Vaporized> = field.vaporize(&station, Laser::default()).collect();
+        let overlay = svg(&field, Some(station), Some(&order));
+        assert_eq!(overlay.matches("font-size=\"8\"").count(), 9);
+    }
+}
@@ -0,0 +1,351 @@
+//! Times each day's parse, part 1 and part 2 phases, and compares results between runs.
+//!
+//! Results are stored as tab-separated values, one line per day and phase, with times in
+//! nanoseconds, so they can be kept between runs or loaded into a spreadsheet.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::Runner;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+pub const TSV_HEADER: &str = "day\tphase\truns\tmin_ns\tmedian_ns\tmean_ns\tmax_ns";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.iter().copied().find(|p| p.name() == name)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Summary of repeated timings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        Stats {
+            runs: n,
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            max: sorted[n - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u32,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Untimed runs before measuring, to warm caches.
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            warmup: 2,
+            runs: 10,
+        }
+    }
+}
+
+/// Times each phase of one solution on the given input.
+pub fn bench_solution(
+    solution: &dyn Runner,
+    input: &str,
+    config: &Config,
+) -> Result<Vec<Measurement>> {
+    for _ in 0..config.warmup {
+        solution.time_phases(input)?;
+    }
+    let mut samples = vec![Vec::new(); Phase::ALL.len()];
+    for _ in 0..config.runs.max(1) {
+        for (i, time) in solution.time_phases(input)?.iter().enumerate() {
+            samples[i].push(*time);
+        }
+    }
+    Ok(Phase::ALL
+        .iter()
+        .zip(samples.iter())
+        .map(|(phase, samples)| Measurement {
+            day: solution.day(),
+            phase: *phase,
+            stats: Stats::from_samples(samples),
+        })
+        .collect())
+}
+
+/// Times every given solution on its default input.
+pub fn run(solutions: &[Box<dyn Runner>], config: &Config) -> Result<Vec<Measurement>> {
+    let mut results = Vec::new();
+    for solution in solutions.iter() {
+        let input = input::read(&solution.default_input())?;
+        results.extend(input.parse(|text| bench_solution(solution.as_ref(), text, config))?);
+    }
+    Ok(results)
+}
+
+pub fn to_tsv(results: &[Measurement]) -> String {
+    let mut out = format!("{}\n", TSV_HEADER);
+    for m in results.iter() {
+        let s = &m.stats;
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            m.day,
+            m.phase,
+            s.runs,
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.mean.as_nanos(),
+            s.max.as_nanos()
+        ));
+    }
+    out
+}
+
+/// Reads results written by `to_tsv`.
+pub fn parse_tsv(text: &str) -> Result<Vec<Measurement>> {
+    let mut results = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line == TSV_HEADER {
+            continue;
+        }
+        let err = |msg: &str| Error::at_line(i + 1, msg);
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 7 {
+            return Err(err("expected 7 fields"));
+        }
+        let day = fields[0].parse().map_err(|_| err("invalid day"))?;
+        let phase = Phase::from_name(fields[1]).ok_or_else(|| err("invalid phase"))?;
+        let runs = fields[2].parse().map_err(|_| err("invalid run count"))?;
+        let mut times = [Duration::default(); 4];
+        for (time, field) in times.iter_mut().zip(fields[3..].iter()) {
+            *time = Duration::from_nanos(field.parse().map_err(|_| err("invalid time"))?);
+        }
+        results.push(Measurement {
+            day,
+            phase,
+            stats: Stats {
+                runs,
+                min: times[0],
+                median: times[1],
+                mean: times[2],
+                max: times[3],
+            },
+        });
+    }
+    Ok(results)
+}
+
+/// Median times of one phase in two sets of results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub phase: Phase,
+    pub old: Duration,
+    pub new: Duration,
+}
+
+impl Comparison {
+    /// New time relative to old, e.g. 1.5 for 50% slower.
+    pub fn ratio(&self) -> f64 {
+        self.new.as_secs_f64() / self.old.as_secs_f64().max(1e-9)
+    }
+
+    /// True if the new time is more than `threshold` (e.g. 0.1 for 10%) slower.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.ratio() > 1.0 + threshold
+    }
+}
+
+/// Pairs up phases present in both result sets, in the order of `new`.
+pub fn compare(old: &[Measurement], new: &[Measurement]) -> Vec<Comparison> {
+    let old: HashMap<(u32, Phase), Duration> = old
+        .iter()
+        .map(|m| ((m.day, m.phase), m.stats.median))
+        .collect();
+    new.iter()
+        .filter_map(|m| {
+            old.get(&(m.day, m.phase)).map(|&old| Comparison {
+                day: m.day,
+                phase: m.phase,
+                old,
+                new: m.stats.median,
+            })
+        })
+        .collect()
+}
+
+/// Human-readable table of results.
+pub fn format_results(results: &[Measurement]) -> String {
+    let mut out = String::new();
+    for m in results.iter() {
+        let s = &m.stats;
+        out.push_str(&format!(
+            "day {:>2} {:<5}  median {:>10.3?}  min {:>10.3?}  max {:>10.3?}\n",
+            m.day, m.phase, s.median, s.min, s.max
+        ));
+    }
+    out
+}
+
+/// Human-readable table of comparisons, marking regressions above `threshold`.
+pub fn format_comparisons(comparisons: &[Comparison], threshold: f64) -> String {
+    let mut out = String::new();
+    for c in comparisons.iter() {
+        let line = format!(
+            "day {:>2} {:<5}  {:>10.3?} -> {:>10.3?}  {:+7.1}%  {}",
+            c.day,
+            c.phase,
+            c.old,
+            c.new,
+            (c.ratio() - 1.0) * 100.0,
+            if c.is_regression(threshold) {
+                "REGRESSION"
+            } else {
+                ""
+            }
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Fake;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn measurement(day: u32, phase: Phase, median: Duration) -> Measurement {
+        Measurement {
+            day,
+            phase,
+            stats: Stats::from_samples(&[median]),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let s = Stats::from_samples(&[ms(5), ms(1), ms(3)]);
+        assert_eq!(
+            (s.runs, s.min, s.median, s.mean, s.max),
+            (3, ms(1), ms(3), ms(3), ms(5))
+        );
+        let s = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!((s.median, s.mean), (ms(3), ms(4)));
+    }
+
+    #[test]
+    fn test_bench_solution() {
+        let config = Config { warmup: 1, runs: 3 };
+        let results = bench_solution(&Fake, "21", &config).unwrap();
+        let phases: Vec<Phase> = results.iter().map(|m| m.phase).collect();
+        assert_eq!(phases, Phase::ALL.to_vec());
+        assert!(results.iter().all(|m| m.day == 99 && m.stats.runs == 3));
+        assert_eq!(
+            bench_solution(&Fake, "x", &config).unwrap_err().to_string(),
+            "not a number"
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        let results = vec![
+            Measurement {
+                day: 12,
+                phase: Phase::Part2,
+                stats: Stats::from_samples(&[ms(20), ms(30), ms(25)]),
+            },
+            measurement(3, Phase::Parse, Duration::from_nanos(1234)),
+        ];
+        let tsv = to_tsv(&results);
+        assert!(tsv.starts_with(TSV_HEADER));
+        assert!(tsv.contains("12\tpart2\t3\t20000000\t25000000\t25000000\t30000000\n"));
+        assert_eq!(parse_tsv(&tsv).unwrap(), results);
+
+        assert_eq!(
+            parse_tsv("1\tparse\t1").unwrap_err().to_string(),
+            "line 1: expected 7 fields"
+        );
+        assert_eq!(
+            parse_tsv("1\tpart3\t1\t1\t1\t1\t1")
+                .unwrap_err()
+                .to_string(),
+            "line 1: invalid phase"
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let old = vec![
+            measurement(1, Phase::Part1, ms(10)),
+            measurement(1, Phase::Part2, ms(10)),
+        ];
+        let new = vec![
+            measurement(1, Phase::Part1, ms(12)),
+            measurement(1, Phase::Part2, ms(10)),
+            measurement(2, Phase::Part1, ms(50)),
+        ];
+        let comparisons = compare(&old, &new);
+        assert_eq!(comparisons.len(), 2);
+        assert!(comparisons[0].is_regression(0.1));
+        assert!(!comparisons[0].is_regression(0.25));
+        assert!(!comparisons[1].is_regression(0.0));
+
+        let text = format_comparisons(&comparisons, 0.1);
+        assert!(text.lines().next().unwrap().ends_with("+20.0%  REGRESSION"));
+        assert!(text.lines().nth(1).unwrap().ends_with("+0.0%"));
+    }
+}
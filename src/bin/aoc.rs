@@ -0,0 +1,262 @@
+// Runs any day's solution.
+//
+// Usage: aoc <day> [--part 1|2] [--input FILE|-] [--json]
+//        aoc check [--answers FILE] [--record] [DAY...]
+//        aoc bench [--runs N] [--warmup N] [--out FILE] [--compare FILE] [--threshold PCT] [DAY...]
+//
+// `check` runs solutions on their default inputs and compares them with the expected answers;
+// `--record` writes the current answers to the answers file instead.
+//
+// `bench` times each phase of each day, optionally saving results as TSV and comparing medians
+// with an earlier results file. It exits with an error if any phase got slower than the
+// threshold (default 10%).
+
+extern crate adv_2019;
+
+use adv_2019::bench;
+use adv_2019::input;
+use adv_2019::regression::{self, Answers, Status};
+use adv_2019::solution::{self, Part};
+use std::env::args;
+use std::fs;
+use std::process::exit;
+
+const USAGE: &str = "usage: aoc <day> [--part 1|2] [--input FILE|-] [--json]
+       aoc check [--answers FILE] [--record] [DAY...]
+       aoc bench [--runs N] [--warmup N] [--out FILE] [--compare FILE] [--threshold PCT] [DAY...]";
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    exit(2);
+}
+
+struct Options {
+    day: u32,
+    parts: Vec<Part>,
+    input: Option<String>,
+    json: bool,
+}
+
+fn parse_args() -> Options {
+    let mut args = args().skip(1);
+    let mut day = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = None;
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args
+                    .next()
+                    .and_then(|p| p.parse().ok())
+                    .and_then(Part::from_number)
+                    .unwrap_or_else(|| usage_error("--part must be 1 or 2"));
+                parts = vec![part];
+            }
+            "--input" => {
+                input = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--input needs a file")),
+                );
+            }
+            "--json" => json = true,
+            _ if day.is_none() => {
+                day = Some(
+                    arg.parse()
+                        .unwrap_or_else(|_| usage_error(&format!("invalid day: {}", arg))),
+                );
+            }
+            _ => usage_error(&format!("unexpected argument: {}", arg)),
+        }
+    }
+    Options {
+        day: day.unwrap_or_else(|| usage_error("no day given")),
+        parts,
+        input,
+        json,
+    }
+}
+
+/// Quotes a string for JSON output.
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Solutions for the given days, or all of them if none are given.
+fn find_solutions(days: &[u32]) -> Vec<Box<dyn solution::Runner>> {
+    if days.is_empty() {
+        solution::all()
+    } else {
+        days.iter()
+            .map(|&day| {
+                solution::find(day)
+                    .unwrap_or_else(|| usage_error(&format!("no solution for day {}", day)))
+            })
+            .collect()
+    }
+}
+
+fn parse_day(arg: &str) -> u32 {
+    arg.parse()
+        .unwrap_or_else(|_| usage_error(&format!("invalid day: {}", arg)))
+}
+
+fn check(mut args: impl Iterator<Item = String>) {
+    let mut path = regression::DEFAULT_ANSWERS.to_string();
+    let mut record = false;
+    let mut days = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                path = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--answers needs a file"));
+            }
+            "--record" => record = true,
+            _ => days.push(parse_day(&arg)),
+        }
+    }
+    let solutions = find_solutions(&days);
+    let mut answers = if record && fs::metadata(&path).is_err() {
+        Answers::default()
+    } else {
+        input::or_exit(Answers::from_file(&path))
+    };
+
+    let report = regression::run(&solutions, &answers);
+    println!("{}", report);
+    if record {
+        for o in report.outcomes.iter() {
+            match &o.status {
+                Status::Mismatch { actual, .. } | Status::Unknown { actual } => {
+                    answers.insert(o.day, o.part, actual)
+                }
+                _ => {}
+            }
+        }
+        fs::write(
+            &path,
+            format!("{}\n{}", regression::ANSWERS_HEADER, answers),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            exit(1);
+        });
+        println!("recorded answers in {}", path);
+    } else if !report.ok() {
+        exit(1);
+    }
+}
+
+fn bench(mut args: impl Iterator<Item = String>) {
+    let mut config = bench::Config::default();
+    let mut out = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut days = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .unwrap_or_else(|| usage_error(&format!("{} needs a value", name)))
+        };
+        match arg.as_str() {
+            "--runs" | "--warmup" => {
+                let n = value(&arg)
+                    .parse()
+                    .unwrap_or_else(|_| usage_error(&format!("{} must be a number", arg)));
+                if arg == "--runs" {
+                    config.runs = n;
+                } else {
+                    config.warmup = n;
+                }
+            }
+            "--out" => out = Some(value(&arg)),
+            "--compare" => baseline = Some(value(&arg)),
+            "--threshold" => {
+                threshold = value(&arg)
+                    .trim_end_matches('%')
+                    .parse()
+                    .unwrap_or_else(|_| usage_error("--threshold must be a percentage"));
+            }
+            _ => days.push(parse_day(&arg)),
+        }
+    }
+    let fail = |e: String| -> ! {
+        eprintln!("{}", e);
+        exit(1);
+    };
+    // Read the baseline first, so a bad path doesn't waste a benchmark run.
+    let baseline = baseline.map(|path| input::or_exit(input::load(&path, bench::parse_tsv)));
+
+    let results = input::or_exit(bench::run(&find_solutions(&days), &config));
+    print!("{}", bench::format_results(&results));
+    if let Some(path) = out {
+        fs::write(&path, bench::to_tsv(&results))
+            .unwrap_or_else(|e| fail(format!("{}: {}", path, e)));
+    }
+    if let Some(baseline) = baseline {
+        let threshold = threshold / 100.0;
+        let comparisons = bench::compare(&baseline, &results);
+        println!();
+        print!("{}", bench::format_comparisons(&comparisons, threshold));
+        let regressions = comparisons
+            .iter()
+            .filter(|c| c.is_regression(threshold))
+            .count();
+        if regressions > 0 {
+            println!("{} regressions", regressions);
+            exit(1);
+        }
+    }
+}
+
+fn main() {
+    match args().nth(1).as_deref() {
+        Some("check") => return check(args().skip(2)),
+        Some("bench") => return bench(args().skip(2)),
+        _ => {}
+    }
+    let opts = parse_args();
+    let solution = solution::find(opts.day)
+        .unwrap_or_else(|| usage_error(&format!("no solution for day {}", opts.day)));
+    let path = opts.input.unwrap_or_else(|| solution.default_input());
+    let input = input::or_exit(input::read(&path));
+    let parts = &opts.parts;
+    let answers = input::or_exit(input.parse(|text| solution.run(text, parts)));
+
+    if opts.json {
+        let answers: Vec<String> = opts
+            .parts
+            .iter()
+            .zip(answers.iter())
+            .map(|(part, answer)| {
+                format!(r#"{{"part":{},"answer":{}}}"#, part, json_string(answer))
+            })
+            .collect();
+        println!(
+            r#"{{"day":{},"input":{},"answers":[{}]}}"#,
+            opts.day,
+            json_string(&path),
+            answers.join(",")
+        );
+    } else {
+        for (part, answer) in opts.parts.iter().zip(answers.iter()) {
+            if answer.contains('\n') {
+                println!("day {} part {}:\n{}", opts.day, part, answer.trim_end());
+            } else {
+                println!("day {} part {}: {}", opts.day, part, answer);
+            }
+        }
+    }
+}
@@ -4,13 +4,13 @@
 
 extern crate adv_2019;
 
+use adv_2019::input;
 use adv_2019::intcode::State;
 use std::env::args;
 
 fn main() {
-    // intcode::day2_part2();
     let filename = args().nth(1).expect("no filename given");
-    let mut state = State::from_file(&filename);
+    let mut state = input::or_exit(State::load(&filename));
     for arg in args().skip(2) {
         state.add_input(arg.parse::<i64>().unwrap());
     }
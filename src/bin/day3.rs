@@ -2,11 +2,14 @@
 
 extern crate adv_2019;
 
+use adv_2019::input;
 use adv_2019::wires;
+use adv_2019::wires::wire_set::WireSet;
+use std::env::args;
 use std::fs;
 
 fn main() {
-    let contents = &fs::read_to_string("inputs/day3_1.txt").unwrap();
+    let contents = &input::or_exit(input::read("inputs/day3_1.txt")).text;
     let mut lines = contents.lines();
     let first_line = lines.next().unwrap();
     let second_line = lines.next().unwrap();
@@ -19,4 +22,13 @@ fn main() {
     cursor.go_string(second_line, true);
     dbg!(cursor.smol_manh_distance_collision());
     dbg!(cursor.least_steps_for_collision());
+
+    // Optionally draw the wires: day3 --svg out.svg
+    if args().nth(1).as_deref() == Some("--svg") {
+        let out = args().nth(2).expect("no svg filename given");
+        let mut set = WireSet::new();
+        set.add_string(first_line);
+        set.add_string(second_line);
+        fs::write(&out, wires::svg::render(&set)).unwrap();
+    }
 }
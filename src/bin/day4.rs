@@ -1,73 +1,63 @@
-#[allow(dead_code)]
-
-// Rules:
-// * It is a six-digit number.
-// * The value is within the range given in your puzzle input.
-// * Two adjacent digits are the same (like 22 in 122345).
-// * Going from left to right, the digits never decrease; they only ever increase or stay the same
-//   (like 111123 or 135679).
-
-fn has_adjacent_same(digits: &[u32], adjacent_exactly_two: bool) -> bool {
-    let mut count = 1;
-    for i in 1..digits.len() {
-        if digits[i - 1] == digits[i] {
-            count += 1;
-            if !adjacent_exactly_two {
-                return true;
-            }
-        } else {
-            if count == 2 {
-                return true;
-            }
-            count = 1;
-        }
-    }
-    count == 2
+// Day 4 runner.
+//
+// Usage: day4 [--input FILE|-] [--preset 1|2] [--rule RULE]...
+//
+// With no rules, prints the answers to both parts. Otherwise counts the passwords matching the
+// preset's rules plus any extra rules, see `password` for the rule syntax. The range comes from
+// the input file unless a range rule is given.
+
+extern crate adv_2019;
+
+use adv_2019::input;
+use adv_2019::password::{self, Rule, RuleSet};
+use std::env::args;
+use std::process::exit;
+
+const USAGE: &str = "usage: day4 [--input FILE|-] [--preset 1|2] [--rule RULE]...";
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    exit(2);
 }
 
-fn digits_decrease(digits: &[u32]) -> bool {
-    for i in 1..digits.len() {
-        if digits[i - 1] < digits[i] {
-            return false;
+fn main() {
+    let mut path = "inputs/day4.txt".to_string();
+    let mut preset = None;
+    let mut rules = Vec::new();
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error(&format!("{} needs a value", arg)))
+        };
+        match arg.as_str() {
+            "--input" => path = value(),
+            "--preset" => preset = Some(value()),
+            "--rule" => rules.push(
+                value()
+                    .parse::<Rule>()
+                    .unwrap_or_else(|e| usage_error(&e.to_string())),
+            ),
+            _ => usage_error(&format!("unexpected argument: {}", arg)),
         }
     }
-    true
-}
+    let (from, to) = input::or_exit(input::load(&path, password::parse_range));
 
-fn is_good(mut num: u32, adjacent_exactly_two: bool) -> bool {
-    // Digits in reverse.
-    let mut digits = vec![0; 6];
-    for i in 0..6 {
-        digits[i] = num % 10;
-        num /= 10;
+    if preset.is_none() && rules.is_empty() {
+        println!("part 1: {}", RuleSet::part1(from, to).count().unwrap());
+        println!("part 2: {}", RuleSet::part2(from, to).count().unwrap());
+        return;
     }
-    if !has_adjacent_same(digits.as_slice(), adjacent_exactly_two) {
-        return false;
+    let mut set = match preset.as_deref() {
+        Some("1") => RuleSet::part1(from, to),
+        Some("2") => RuleSet::part2(from, to),
+        Some(other) => usage_error(&format!("unknown preset: {}", other)),
+        None if rules.iter().any(|r| matches!(r, Rule::Range(..))) => RuleSet::new(),
+        None => RuleSet::new().with(Rule::Range(from, to)),
+    };
+    for rule in rules {
+        set.push(rule);
     }
-    digits_decrease(digits.as_slice())
-}
-
-fn scan(from: u32, to: u32, adjacent_exactly_two: bool) -> u32 {
-    let mut good = 0;
-    for i in from..=to {
-        if is_good(i, adjacent_exactly_two) {
-            good += 1;
-        }
-    }
-    good
-}
-
-fn part1(from: u32, to: u32) -> u32 {
-    scan(from, to, false)
-}
-
-fn part2(from: u32, to: u32) -> u32 {
-    scan(from, to, true)
-}
-
-fn main() {
-    let from = 109165;
-    let to = 576723;
-    dbg!(part1(from, to));
-    dbg!(part2(from, to));
+    println!("rules: {}", set);
+    println!("{}", input::or_exit(set.count()));
 }
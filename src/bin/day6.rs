@@ -1,81 +1,95 @@
 // Day 6 solution.
+//
+// Usage: day6 [--input FILE|-] [--validate | --dot [--highlight FROM:TO] | --tree]
+//
+// --validate prints the roots, separate systems, duplicate parents and cycles instead of
+// solving, and exits with an error if the map isn't a single tree.
+//
+// --dot prints the map for Graphviz, e.g. `day6 --dot --highlight YOU:SAN | dot -Tsvg`, with the
+// path between the two bodies in red. --tree prints an indented outline with subtree sizes.
 
-#![allow(dead_code)]
+extern crate adv_2019;
 
-use std::collections::HashMap;
-use std::fs;
+use adv_2019::days::day6::Orbits;
+use adv_2019::input;
+use adv_2019::tree::export;
+use std::env::args;
+use std::process::exit;
 
-struct Orbits {
-    parents: HashMap<String, String>,
-    distance_to_san: HashMap<String, i32>,
+const USAGE: &str =
+    "usage: day6 [--input FILE|-] [--validate | --dot [--highlight FROM:TO] | --tree]";
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    exit(2);
 }
 
-impl Orbits {
-    fn parse(file: &str) -> Orbits {
-        let input = fs::read_to_string(file).unwrap();
-        let mut parents = HashMap::new();
-        for line in input.lines() {
-            assert_eq!(line.len(), 7);
-            assert_eq!(line.chars().nth(3).unwrap(), ')');
-            // We want to create links from rhs to lhs, so we add lhs to the list of rhs.
-            let lhs = &line[0..3];
-            let rhs = &line[4..7];
-            assert!(!parents.contains_key(&rhs.to_string()));
-            parents.insert(rhs.to_string(), lhs.to_string());
-        }
-        Orbits {
-            parents,
-            distance_to_san: HashMap::new(),
+fn main() {
+    let mut path = "inputs/day6.txt".to_string();
+    let mut validate = false;
+    let mut dot = false;
+    let mut highlight = None;
+    let mut outline = false;
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                path = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input needs a file"))
+            }
+            "--validate" => validate = true,
+            "--dot" => dot = true,
+            "--highlight" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--highlight needs FROM:TO"));
+                match value.split_once(':') {
+                    Some((from, to)) => highlight = Some((from.to_string(), to.to_string())),
+                    None => usage_error("--highlight needs FROM:TO"),
+                }
+            }
+            "--tree" => outline = true,
+            _ => usage_error(&format!("unexpected argument: {}", arg)),
         }
     }
 
-    fn mark_distances_to_san(&mut self) {
-        let mut cnt = 0;
-        let mut node = self.parents.get("SAN").unwrap();
-        while self.parents.contains_key(node) {
-            self.distance_to_san.insert(node.to_string(), cnt);
-            cnt += 1;
-            node = self.parents.get(node).unwrap();
+    if validate {
+        let report = input::or_exit(input::load(&path, Orbits::validate));
+        println!("{}", report);
+        if !report.is_tree() {
+            exit(1);
         }
+        return;
     }
-
-    fn find_distance_to_san(&self) -> i32 {
-        let mut cnt = 0;
-        let mut node = self.parents.get("YOU").unwrap();
-        while self.parents.contains_key(node) {
-            if self.distance_to_san.contains_key(node) {
-                return cnt + self.distance_to_san.get(node).unwrap();
+    let orbits = input::or_exit(input::load(&path, Orbits::parse));
+    let tree = orbits.tree();
+    if dot {
+        let path = match &highlight {
+            Some((from, to)) => {
+                let id = |name: &str| {
+                    tree.id(name).unwrap_or_else(|| {
+                        eprintln!("no body named {}", name);
+                        exit(1)
+                    })
+                };
+                tree.path(id(from), id(to)).unwrap_or_else(|| {
+                    eprintln!("{} and {} aren't in the same system", from, to);
+                    exit(1)
+                })
             }
-            cnt += 1;
-            node = self.parents.get(node).unwrap();
-        }
-        panic!("failed to find");
+            None => Vec::new(),
+        };
+        print!("{}", export::dot(tree, &path));
+        return;
     }
-
-    fn count_up(&self, key: &str) -> i32 {
-        let mut cnt = 0;
-        let mut node = key;
-        while self.parents.contains_key(node) {
-            cnt += 1;
-            node = self.parents.get(node).unwrap();
-        }
-        cnt
+    if highlight.is_some() {
+        usage_error("--highlight only works with --dot");
     }
-
-    fn count_orbits(&self) -> i32 {
-        let mut cnt = 0;
-        for from in self.parents.keys() {
-            // dbg!(from);
-            cnt += self.count_up(from);
-        }
-        cnt
+    if outline {
+        print!("{}", export::indented(tree));
+        return;
     }
-}
-
-fn main() {
-    let mut orbits = Orbits::parse("inputs/day6.txt");
     dbg!(orbits.count_orbits());
-    orbits.mark_distances_to_san();
-    // dbg!(orbits.distance_to_san);
-    dbg!(orbits.find_distance_to_san());
+    dbg!(orbits.transfers("YOU", "SAN"));
 }
@@ -1,80 +1,95 @@
 // Day 7 runner.
-
-#![allow(dead_code)]
+//
+// Usage: day7 [--input FILE|-] [--amps N] [--topology chain|ring] [--signal N] [--phases PHASES]
+//
+// Finds the phase assignment giving the highest signal. PHASES is a range like 5-9 or a list like
+// 0,1,2,3,4; it defaults to 0-4 for a chain and 5-9 for a ring. There's one amplifier per phase
+// unless --amps says fewer, in which case each subset of phases is tried. With no options prints
+// the answers to both parts.
 
 extern crate adv_2019;
 
-use adv_2019::intcode::{State, StopReason};
+use adv_2019::days::day7::{best_signal, AmpConfig, Topology};
+use adv_2019::input;
+use adv_2019::intcode::State;
 use std::env::args;
+use std::process::exit;
 
-fn run(combo: &[i64], base: &State) -> i64 {
-    let mut states = Vec::new();
-    for i in 0..5 {
-        let mut state = base.clone();
-        state.add_input(combo[i]);
-        if i == 0 {
-            state.add_input(0);
-        }
-        states.push(state);
-    }
-    let mut to_run = 0;
-    loop {
-        let stop_reason = states[to_run].run(false);
-        // dbg!(&to_run, &stop_reason);
-        match stop_reason {
-            StopReason::Done => {
-                if to_run == 4 {
-                    let outputs = states[to_run].outputs();
-                    assert_eq!(outputs.len(), 1);
-                    return outputs.pop_front().unwrap();
-                }
-            }
-            _ => {}
-        };
-        let next = (to_run + 1) % 5;
-        while let Some(value) = states[to_run].outputs().pop_front() {
-            states[next].add_input(value);
+const USAGE: &str = "usage: day7 [--input FILE|-] [--amps N] [--topology chain|ring] [--signal N] \
+                     [--phases PHASES]";
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    exit(2);
+}
+
+fn number<T: std::str::FromStr>(arg: &str, value: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| usage_error(&format!("{} needs a number, not {}", arg, value)))
+}
+
+/// `5-9` or `0,1,2,3,4`.
+fn parse_phases(value: &str) -> Vec<i64> {
+    match value.split_once('-') {
+        Some((from, to)) if !from.is_empty() => {
+            (number::<i64>("--phases", from)..=number("--phases", to)).collect()
         }
-        to_run = next;
+        _ => value.split(',').map(|p| number("--phases", p)).collect(),
     }
 }
 
 fn main() {
-    let filename = args().nth(1).expect("no filename given");
-    let base_state = State::from_file(&filename);
-    let phase_min = args()
-        .nth(2)
-        .expect("no phase start given")
-        .parse::<i64>()
-        .unwrap();
-    let phases = phase_min..(phase_min + 5);
-
-    let mut best = 0;
-    for q in phases.clone() {
-        for w in phases.clone() {
-            if q == w {
-                continue;
-            }
-            for e in phases.clone() {
-                if q == e || w == e {
-                    continue;
-                }
-                for r in phases.clone() {
-                    if q == r || w == r || e == r {
-                        continue;
-                    }
-                    for t in phases.clone() {
-                        if q == t || w == t || e == t || r == t {
-                            continue;
-                        }
-                        let val = run(&[q, w, e, r, t], &base_state);
-                        if val > best {
-                            best = val;
-                            dbg!(val, q, w, e, r, t);
-                        }
-                    }
-                }
+    let mut path = "inputs/day7.txt".to_string();
+    let mut count = None;
+    let mut topology = None;
+    let mut signal = None;
+    let mut phases = None;
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error(&format!("{} needs a value", arg)))
+        };
+        match arg.as_str() {
+            "--input" => path = value(),
+            "--amps" => count = Some(number(&arg, &value())),
+            "--topology" => {
+                topology = Some(match value().as_str() {
+                    "chain" => Topology::Chain,
+                    "ring" => Topology::Ring,
+                    other => usage_error(&format!("unknown topology: {}", other)),
+                })
             }
+            "--signal" => signal = Some(number(&arg, &value())),
+            "--phases" => phases = Some(parse_phases(&value())),
+            _ => usage_error(&format!("unexpected argument: {}", arg)),
+        }
+    }
+    let state = input::or_exit(State::load(&path));
+
+    let configs = if count.is_none() && topology.is_none() && signal.is_none() && phases.is_none() {
+        vec![AmpConfig::part1(), AmpConfig::part2()]
+    } else {
+        let mut config = match topology {
+            Some(Topology::Ring) => AmpConfig::part2(),
+            _ => AmpConfig::part1(),
+        };
+        if let Some(phases) = phases {
+            config.count = phases.len();
+            config.phases = phases;
+        }
+        config.count = count.unwrap_or(config.count);
+        config.initial_signal = signal.unwrap_or(config.initial_signal);
+        vec![config]
+    };
+    for config in configs {
+        match input::or_exit(best_signal(&state, &config)) {
+            Some(best) => println!(
+                "{:?}: {} with phases {:?}",
+                config.topology, best.signal, best.phases
+            ),
+            None => println!("{:?}: no signal", config.topology),
         }
     }
 }
@@ -1,100 +1,135 @@
-use std::env::args;
-use std::fs;
-
-struct Layer {
-    pixels: Vec<char>,
-}
+// Day 8 runner.
+//
+// Usage: day8 [--input FILE|-] [--size WxH] [--checksum FEWEST:A*B] [--layer N]
+//             [--histogram | --depth | --export FILE [--scale N]]
+//
+// Prints the part 1 checksum, the decoded image in block characters and the text it spells.
+// Either side of --size can be ?, e.g. 25x? or ?x?, to work it out from the data. --checksum
+// changes the colors in the checksum, see `sif::Checksum`. --layer shows a single layer instead
+// of all of them stacked.
+//
+// --histogram prints the pixel counts of each layer instead, and --depth which layer each
+// visible pixel comes from. --export saves the image, as PBM, PGM or PNG depending on the file's
+// extension, with each pixel scaled up to N x N (default 10).
 
-impl Layer {
-    fn parse(chars: &[char]) -> Self {
-        let pixels = chars.iter().cloned().collect();
-        Layer { pixels }
-    }
+extern crate adv_2019;
 
-    fn count_color(&self, color: char) -> usize {
-        self.pixels
-            .iter()
-            .fold(0, |acc, char| acc + if *char == color { 1 } else { 0 })
-    }
+use adv_2019::days::day8::{HEIGHT, WIDTH};
+use adv_2019::input;
+use adv_2019::sif::{export, Checksum, Image};
+use std::env::args;
+use std::fs;
+use std::process::exit;
 
-    fn merge_layer_under(&self, other: &Layer) -> Layer {
-        let pixels: Vec<char> = self
-            .pixels
-            .iter()
-            .zip(other.pixels.iter())
-            .map(|(a, b)| if *a == '2' { *b } else { *a })
-            .collect();
-        Layer { pixels }
-    }
+const USAGE: &str = "usage: day8 [--input FILE|-] [--size WxH] [--checksum FEWEST:A*B] \
+                     [--layer N] [--histogram | --depth | --export FILE [--scale N]]";
 
-    fn render(&self, width: usize) {
-        for row in self.pixels.chunks(width) {
-            dbg!(row.iter().collect::<String>());
-        }
-    }
+fn usage_error(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    exit(2);
 }
 
-struct Image {
-    layers: Vec<Layer>,
-    width: usize,
+fn number(arg: &str, value: &str) -> usize {
+    value
+        .parse()
+        .unwrap_or_else(|_| usage_error(&format!("{} needs a number, not {}", arg, value)))
 }
 
-impl Image {
-    fn find_layer_with_fewest_of_color(&self, color: char) -> usize {
-        let mut lowest = (0, std::usize::MAX);
-        for i in 0..self.layers.len() {
-            let this = self.layers[i].count_color(color);
-            if this < lowest.1 {
-                lowest = (i, this);
+fn main() {
+    let mut path = "inputs/day8.txt".to_string();
+    let (mut width, mut height) = (Some(WIDTH), Some(HEIGHT));
+    let mut checksum = Checksum::part1();
+    let mut layer = None;
+    let mut histogram = false;
+    let mut depth = false;
+    let mut out = None;
+    let mut scale = 10;
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error(&format!("{} needs a value", arg)))
+        };
+        match arg.as_str() {
+            "--input" => path = value(),
+            "--size" => {
+                let size = value();
+                match size.split_once('x') {
+                    Some((w, h)) => {
+                        let side = |v: &str| {
+                            if v == "?" {
+                                None
+                            } else {
+                                Some(number(&arg, v))
+                            }
+                        };
+                        width = side(w);
+                        height = side(h);
+                    }
+                    None => usage_error("--size needs WxH"),
+                }
             }
+            "--checksum" => {
+                checksum = value()
+                    .parse()
+                    .unwrap_or_else(|e: adv_2019::Error| usage_error(&e.to_string()))
+            }
+            "--layer" => layer = Some(number(&arg, &value())),
+            "--histogram" => histogram = true,
+            "--depth" => depth = true,
+            "--export" => out = Some(value()),
+            "--scale" => scale = number(&arg, &value()),
+            _ => usage_error(&format!("unexpected argument: {}", arg)),
         }
-        lowest.0
     }
-
-    fn parse(file: &str, width: usize, height: usize) -> Self {
-        let input = fs::read_to_string(file).unwrap();
-        // Make sure there's exactly one line and it has a good number of characters.
-        let lines: Vec<&str> = input.lines().collect();
-        assert_eq!(lines.len(), 1);
-        let pixels: Vec<char> = lines[0].trim().chars().collect();
-        assert_eq!(pixels.len() % (width * height), 0);
-
-        let layers = pixels
-            .chunks(width * height)
-            .map(|chunk| Layer::parse(chunk))
-            .collect();
-        Image { layers, width }
+    if scale == 0 {
+        usage_error("--scale must be at least 1");
     }
-
-    fn layer(&self, i: usize) -> &Layer {
-        &self.layers[i]
+    let img = input::or_exit(input::load(&path, |text| Image::infer(text, width, height)));
+    if histogram {
+        for (i, h) in img.histograms().iter().enumerate() {
+            println!("layer {:3}: {}", i, h);
+        }
+        println!("total:     {}", img.histogram());
+        return;
+    }
+    if depth {
+        print!("{}", img.render_depths());
+        return;
     }
+    let shown = match layer {
+        Some(i) if i >= img.layers().len() => {
+            eprintln!("no layer {}, the image has {}", i, img.layers().len());
+            exit(1);
+        }
+        Some(i) => img.layer(i).clone(),
+        None => img.merged(),
+    };
 
-    fn show_merged(&self) {
-        let mut merged = self.layers[0].merge_layer_under(&self.layers[1]);
-        for i in 2..self.layers.len() {
-            merged = merged.merge_layer_under(&self.layers[i]);
+    let out = match out {
+        Some(out) => out,
+        None => {
+            if width.is_none() || height.is_none() {
+                println!("size: {}x{}", img.width(), img.height());
+            }
+            println!("checksum {}: {}", checksum, checksum.apply(&img));
+            print!("{}", shown.blocks());
+            let reading = shown.text();
+            println!("text: {}", reading.text);
+            for u in reading.unknown.iter() {
+                eprintln!("unknown glyph at column {}:\n{}", u.column, u.art);
+            }
+            return;
         }
-        // dbg!(merged);
-        merged.render(self.width);
+    };
+    let data = match out.rsplit('.').next() {
+        Some("pbm") => export::pbm(&shown, scale).into_bytes(),
+        Some("pgm") => export::pgm(&shown, scale).into_bytes(),
+        Some("png") => export::png(&shown, scale),
+        _ => usage_error("--export needs a .pbm, .pgm or .png file"),
+    };
+    if let Err(e) = fs::write(&out, data) {
+        eprintln!("{}: {}", out, e);
+        exit(1);
     }
 }
-
-fn main() {
-    let filename = args().nth(1).expect("no filename given");
-    let width = args()
-        .nth(2)
-        .expect("no width given")
-        .parse::<usize>()
-        .unwrap();
-    let height = args()
-        .nth(3)
-        .expect("no height given")
-        .parse::<usize>()
-        .unwrap();
-    let img = Image::parse(&filename, width, height);
-    let zeroes_layer_idx = img.find_layer_with_fewest_of_color('0');
-    let zeroes_layer = img.layer(zeroes_layer_idx);
-    dbg!(zeroes_layer.count_color('1') * zeroes_layer.count_color('2'));
-    img.show_merged();
-}
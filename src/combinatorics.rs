@@ -0,0 +1,447 @@
+//! Lazy enumeration of arrangements of items, for brute-force searches.
+//!
+//! Besides iterating from the start, each iterator can `seek` straight to the n-th arrangement.
+//! `chunks` uses that to split a search into independent pieces, e.g. one per thread, without
+//! generating the arrangements before each piece.
+
+use std::iter::Take;
+use std::ops::Range;
+
+/// Iterators that can jump to any position without producing the items before it.
+pub trait Seek: Iterator + Clone {
+    /// Number of items yielded from the start, None if more than `usize::MAX`.
+    fn total(&self) -> Option<usize>;
+
+    /// Moves to the item at `rank`, counting from the start. Past the end the iterator is
+    /// exhausted.
+    fn seek(&mut self, rank: usize);
+}
+
+/// Splits `0..total` into at most `parts` contiguous ranges, whose lengths differ by at most one.
+/// Never returns empty ranges.
+pub fn split(total: usize, parts: usize) -> Vec<Range<usize>> {
+    let parts = parts.clamp(1, total.max(1));
+    let (size, extra) = (total / parts, total % parts);
+    let mut start = 0;
+    (0..parts)
+        .map(|i| {
+            let len = size + usize::from(i < extra);
+            start += len;
+            start - len..start
+        })
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+/// Splits everything `iter` yields from the start into at most `parts` iterators, which together
+/// yield the same items in the same order. Panics if `iter` has more than `usize::MAX` items.
+pub fn chunks<I: Seek>(iter: &I, parts: usize) -> Vec<Take<I>> {
+    let total = iter.total().expect("too many items to split into chunks");
+    split(total, parts)
+        .into_iter()
+        .map(|range| {
+            let mut chunk = iter.clone();
+            chunk.seek(range.start);
+            chunk.take(range.len())
+        })
+        .collect()
+}
+
+fn factorial(n: usize) -> Option<usize> {
+    (1..=n).try_fold(1usize, |acc, i| acc.checked_mul(i))
+}
+
+/// Number of ways to choose `k` of `n` items, None on overflow.
+pub fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    // Each partial product is itself a binomial coefficient, so the division is exact.
+    (0..k).try_fold(1usize, |acc, i| Some(acc.checked_mul(n - i)? / (i + 1)))
+}
+
+/// Iterator over all orderings of some items, see `permutations`.
+#[derive(Debug, Clone)]
+pub struct Permutations<T> {
+    /// The items as given, for seeking.
+    original: Vec<T>,
+    items: Vec<T>,
+    /// Heap's algorithm state: a counter per level.
+    counters: Vec<usize>,
+    level: usize,
+    first: bool,
+}
+
+/// All orderings of `items`, generated lazily with Heap's algorithm: each one differs from the
+/// previous by a single swap. Yields n! orderings; one empty ordering if `items` is empty.
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        original: items.to_vec(),
+        items: items.to_vec(),
+        counters: vec![0; items.len()],
+        level: 1,
+        first: true,
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.first {
+            self.first = false;
+            return Some(self.items.clone());
+        }
+        while self.level < self.items.len() {
+            let i = self.level;
+            if self.counters[i] < i {
+                let j = if i.is_multiple_of(2) {
+                    0
+                } else {
+                    self.counters[i]
+                };
+                self.items.swap(j, i);
+                self.counters[i] += 1;
+                self.level = 1;
+                return Some(self.items.clone());
+            }
+            self.counters[i] = 0;
+            self.level += 1;
+        }
+        None
+    }
+}
+
+/// Position mapping of a full run of Heap's algorithm over the first `k` items: afterwards,
+/// position p holds the item that was at `heap_run(k)[p]`.
+fn heap_run(k: usize) -> Vec<usize> {
+    let mut items: Vec<usize> = (0..k).collect();
+    if k > 1 {
+        let inner = heap_run(k - 1);
+        for i in 0..k {
+            apply(&mut items, &inner);
+            if i + 1 < k {
+                heap_swap(&mut items, k - 1, i);
+            }
+        }
+    }
+    items
+}
+
+/// The swap Heap's algorithm makes at `level` after `done` passes through the levels below.
+fn heap_swap<T>(items: &mut [T], level: usize, done: usize) {
+    let j = if level.is_multiple_of(2) { 0 } else { done };
+    items.swap(j, level);
+}
+
+/// Rearranges the start of `items` as described by a `heap_run` mapping.
+fn apply<T: Clone>(items: &mut [T], mapping: &[usize]) {
+    let before: Vec<T> = items[..mapping.len()].to_vec();
+    for (p, from) in mapping.iter().enumerate() {
+        items[p] = before[*from].clone();
+    }
+}
+
+impl<T: Clone> Seek for Permutations<T> {
+    fn total(&self) -> Option<usize> {
+        factorial(self.items.len())
+    }
+
+    fn seek(&mut self, mut rank: usize) {
+        let n = self.items.len();
+        self.items = self.original.clone();
+        self.level = 1;
+        self.first = true;
+        if self.total().is_some_and(|total| rank >= total) {
+            // Exhausted: every counter at its maximum.
+            self.counters = (0..n).collect();
+            self.level = n;
+            self.first = false;
+            return;
+        }
+        // After the r-th ordering, the counters hold r in the factorial number system, counter i
+        // being the digit with radix i + 1.
+        for i in 1..n {
+            self.counters[i] = rank % (i + 1);
+            rank /= i + 1;
+        }
+        for level in (1..n).rev() {
+            let inner = heap_run(level);
+            for done in 0..self.counters[level] {
+                apply(&mut self.items, &inner);
+                heap_swap(&mut self.items, level, done);
+            }
+        }
+    }
+}
+
+/// Iterator over ways to choose some of the items, see `combinations`.
+#[derive(Debug, Clone)]
+pub struct Combinations<T> {
+    items: Vec<T>,
+    k: usize,
+    /// Indices of the next choice, increasing. None when done.
+    indices: Option<Vec<usize>>,
+}
+
+/// All ways to choose `k` of `items`, keeping their order, in lexicographic order of positions:
+/// [a, b, c] choose 2 gives [a, b], [a, c], [b, c]. Yields nothing if `k` is more than the number
+/// of items, and one empty choice if `k` is 0.
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<T> {
+    Combinations {
+        items: items.to_vec(),
+        k,
+        indices: if k <= items.len() {
+            Some((0..k).collect())
+        } else {
+            None
+        },
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = &self.items;
+        let indices = self.indices.as_mut()?;
+        let choice = indices.iter().map(|i| items[*i].clone()).collect();
+        // Advance the rightmost index that can move, and pack the ones after it behind it.
+        let (n, k) = (items.len(), self.k);
+        match (0..k).rev().find(|i| indices[*i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(choice)
+    }
+}
+
+impl<T: Clone> Seek for Combinations<T> {
+    fn total(&self) -> Option<usize> {
+        binomial(self.items.len(), self.k)
+    }
+
+    fn seek(&mut self, mut rank: usize) {
+        if self.total().is_some_and(|total| rank >= total) {
+            self.indices = None;
+            return;
+        }
+        let (n, k) = (self.items.len(), self.k);
+        let mut indices = Vec::with_capacity(k);
+        let mut next = 0;
+        for slot in 0..k {
+            // Skip over the choices that put `next` in this slot and pick the rest after it.
+            while let Some(count) = binomial(n - next - 1, k - slot - 1) {
+                if rank < count {
+                    break;
+                }
+                rank -= count;
+                next += 1;
+            }
+            indices.push(next);
+            next += 1;
+        }
+        self.indices = Some(indices);
+    }
+}
+
+/// Iterator over picks of one item from each of several lists, see `product`.
+#[derive(Debug, Clone)]
+pub struct Product<T> {
+    lists: Vec<Vec<T>>,
+    /// Index into each list of the next pick. None when done.
+    indices: Option<Vec<usize>>,
+}
+
+/// All ways to pick one item from each list, in lexicographic order with the last list varying
+/// fastest, like nested loops. Yields nothing if any list is empty, and one empty pick if there
+/// are no lists.
+pub fn product<T: Clone>(lists: &[&[T]]) -> Product<T> {
+    Product {
+        lists: lists.iter().map(|l| l.to_vec()).collect(),
+        indices: if lists.iter().any(|l| l.is_empty()) {
+            None
+        } else {
+            Some(vec![0; lists.len()])
+        },
+    }
+}
+
+impl<T: Clone> Iterator for Product<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let indices = self.indices.as_mut()?;
+        let pick = indices
+            .iter()
+            .zip(self.lists.iter())
+            .map(|(i, list)| list[*i].clone())
+            .collect();
+        // Count like an odometer; done once every wheel wraps around.
+        let lists = &self.lists;
+        let carried = (0..indices.len()).rev().all(|i| {
+            indices[i] += 1;
+            if indices[i] < lists[i].len() {
+                return false;
+            }
+            indices[i] = 0;
+            true
+        });
+        if carried {
+            self.indices = None;
+        }
+        Some(pick)
+    }
+}
+
+impl<T: Clone> Seek for Product<T> {
+    fn total(&self) -> Option<usize> {
+        self.lists
+            .iter()
+            .try_fold(1usize, |acc, l| acc.checked_mul(l.len()))
+    }
+
+    fn seek(&mut self, mut rank: usize) {
+        if self.total().is_some_and(|total| rank >= total) {
+            self.indices = None;
+            return;
+        }
+        let mut indices = vec![0; self.lists.len()];
+        for (i, list) in self.lists.iter().enumerate().rev() {
+            indices[i] = rank % list.len();
+            rank /= list.len();
+        }
+        self.indices = Some(indices);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_permutations() {
+        let perms: Vec<Vec<i32>> = permutations(&[1, 2, 3]).collect();
+        assert_eq!(
+            perms,
+            vec![
+                vec![1, 2, 3],
+                vec![2, 1, 3],
+                vec![3, 1, 2],
+                vec![1, 3, 2],
+                vec![2, 3, 1],
+                vec![3, 2, 1]
+            ]
+        );
+        assert_eq!(permutations::<i32>(&[]).count(), 1);
+        assert_eq!(permutations(&[7]).collect::<Vec<_>>(), vec![vec![7]]);
+
+        let perms: HashSet<Vec<u8>> = permutations(&[0, 1, 2, 3, 4, 5]).collect();
+        assert_eq!(perms.len(), 720);
+    }
+
+    /// Checks that seeking to every rank, and chunking, agree with plain iteration.
+    fn check_seek<I>(iter: I)
+    where
+        I: Seek,
+        I::Item: PartialEq + std::fmt::Debug,
+    {
+        let all: Vec<I::Item> = iter.clone().collect();
+        assert_eq!(iter.total(), Some(all.len()));
+        for rank in 0..=all.len() + 1 {
+            let mut seeked = iter.clone();
+            seeked.seek(rank);
+            assert_eq!(
+                seeked.collect::<Vec<_>>(),
+                all[rank.min(all.len())..],
+                "rank {}",
+                rank
+            );
+        }
+        for parts in 1..=all.len() + 2 {
+            let chunks = chunks(&iter, parts);
+            assert!(chunks.len() <= parts);
+            let joined: Vec<I::Item> = chunks.into_iter().flatten().collect();
+            assert_eq!(joined, all);
+        }
+    }
+
+    #[test]
+    fn test_permutations_seek() {
+        for n in 0..=5 {
+            let items: Vec<usize> = (0..n).collect();
+            check_seek(permutations(&items));
+        }
+        assert_eq!(permutations(&[0; 21]).total(), None);
+    }
+
+    #[test]
+    fn test_combinations() {
+        let combos: Vec<Vec<char>> = combinations(&['a', 'b', 'c', 'd'], 2).collect();
+        assert_eq!(
+            combos,
+            vec![
+                vec!['a', 'b'],
+                vec!['a', 'c'],
+                vec!['a', 'd'],
+                vec!['b', 'c'],
+                vec!['b', 'd'],
+                vec!['c', 'd']
+            ]
+        );
+        assert_eq!(combinations(&[1, 2], 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(combinations(&[1, 2], 3).count(), 0);
+        for n in 0..=6 {
+            let items: Vec<usize> = (0..n).collect();
+            for k in 0..=n {
+                assert_eq!(combinations(&items, k).count(), binomial(n, k).unwrap());
+                check_seek(combinations(&items, k));
+            }
+        }
+        assert_eq!(binomial(60, 30), Some(118264581564861424));
+        assert_eq!(binomial(200, 100), None);
+    }
+
+    #[test]
+    fn test_product() {
+        let picks: Vec<Vec<i32>> = product(&[&[1, 2][..], &[3], &[4, 5]]).collect();
+        assert_eq!(
+            picks,
+            vec![vec![1, 3, 4], vec![1, 3, 5], vec![2, 3, 4], vec![2, 3, 5]]
+        );
+        assert_eq!(product::<i32>(&[]).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(product(&[&[1, 2][..], &[]]).count(), 0);
+        check_seek(product(&[&[1, 2, 3][..], &[4, 5], &[6, 7, 8, 9]]));
+        check_seek(product(&[&[1, 2][..], &[]]));
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split(10, 3), vec![0..4, 4..7, 7..10]);
+        assert_eq!(split(2, 5), vec![0..1, 1..2]);
+        assert_eq!(split(0, 4), Vec::<Range<usize>>::new());
+        assert_eq!(split(7, 0), vec![0..7]);
+
+        // Chunks can be searched on separate threads.
+        let items: Vec<u64> = (1..=8).collect();
+        let all = permutations(&items);
+        let best = std::thread::scope(|scope| {
+            let handles: Vec<_> = chunks(&all, 4)
+                .into_iter()
+                .map(|chunk| scope.spawn(move || chunk.filter(|p| p[0] < p[7]).count()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .sum::<usize>()
+        });
+        assert_eq!(best, 40320 / 2);
+    }
+}
@@ -0,0 +1,34 @@
+//! Day 10: Monitoring Station. The field itself is in `asteroids`.
+
+use crate::asteroids::{Field, Laser};
+use crate::error::Result;
+use crate::solution::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Field;
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn parse(&self, input: &str) -> Result<Field> {
+        Field::parse(input)
+    }
+
+    fn part1(&self, field: &Field) -> String {
+        let (count, _) = field.find_best_position();
+        count.to_string()
+    }
+
+    fn part2(&self, field: &Field) -> String {
+        let (_, origin) = field.find_best_position();
+        let pt = field
+            .vaporize(&origin, Laser::default())
+            .nth(199)
+            .expect("fewer than 200 asteroids")
+            .pos;
+        (pt.x * 100 + pt.y).to_string()
+    }
+}
@@ -0,0 +1,153 @@
+//! Day 11: Space Police.
+
+use crate::error::Result;
+use crate::geom::{Dir, Point};
+use crate::grid::Grid;
+use crate::intcode::{State, StopReason};
+use crate::ocr;
+use crate::solution::Solution;
+
+type Pos = Point<i64>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    fn from_int(i: i64) -> Self {
+        match i {
+            0 => Self::Black,
+            1 => Self::White,
+            _ => panic!("invalid color: {}", i),
+        }
+    }
+    fn to_int(&self) -> i64 {
+        match self {
+            Self::Black => 0,
+            Self::White => 1,
+        }
+    }
+}
+
+pub struct PaintingState {
+    pub panels: Grid<Color>,
+    pos: Pos,
+    dir: Dir,
+}
+
+impl Default for PaintingState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaintingState {
+    pub fn new() -> Self {
+        Self {
+            panels: Grid::sparse(),
+            pos: Pos::origin(),
+            dir: Dir::U,
+        }
+    }
+
+    fn get_color_as_int(&self, pos: &Pos) -> i64 {
+        match self.panels.get(pos) {
+            Some(c) => c.to_int(),
+            None => 0,
+        }
+    }
+
+    pub fn run(&mut self, mut state: State) {
+        loop {
+            state.add_input(self.get_color_as_int(&self.pos));
+            let stop_reason = state.run(false);
+            let outputs = state.outputs();
+            while !outputs.is_empty() {
+                let color_to_paint = outputs.pop_front().unwrap();
+                self.panels.set(self.pos, Color::from_int(color_to_paint));
+
+                let dir_to_turn = outputs.pop_front().unwrap();
+                match dir_to_turn {
+                    0 => self.dir = self.dir.turn_left(),
+                    1 => self.dir = self.dir.turn_right(),
+                    _ => panic!("invalid dir: {}", dir_to_turn),
+                }
+                self.pos = self.pos.step(self.dir);
+            }
+            match stop_reason {
+                StopReason::Done => {
+                    return;
+                }
+                StopReason::WaitingOnInput => {}
+            }
+        }
+    }
+
+    pub fn count_colored_panels(&self) -> i64 {
+        self.panels.len() as i64
+    }
+
+    /// Reads the letters painted in white.
+    pub fn text(&self) -> ocr::Reading {
+        let rows: Vec<Vec<bool>> = self
+            .panels
+            .rows()
+            .map(|row| row.iter().map(|c| *c == Some(&Color::White)).collect())
+            .collect();
+        ocr::read(&rows)
+    }
+
+    /// Renders white panels as '#'.
+    pub fn render(&self) -> String {
+        self.panels.render(|c| match c {
+            Some(Color::White) => '#',
+            _ => ' ',
+        })
+    }
+}
+
+/// Runs the painting robot starting on a panel of given color.
+pub fn paint(state: &State, start: Color) -> PaintingState {
+    let mut painting_state = PaintingState::new();
+    painting_state.panels.set(Pos::origin(), start);
+    painting_state.run(state.clone());
+    painting_state
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = State;
+
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn parse(&self, input: &str) -> Result<State> {
+        Ok(State::parse(input)?)
+    }
+
+    fn part1(&self, state: &State) -> String {
+        paint(state, Color::Black)
+            .count_colored_panels()
+            .to_string()
+    }
+
+    fn part2(&self, state: &State) -> String {
+        paint(state, Color::White).render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let state = State::from_file("inputs/day11.txt");
+        let painted = paint(&state, Color::White);
+        assert_eq!(painted.text().into_result().unwrap(), "CGPJCGCL");
+    }
+}
@@ -0,0 +1,237 @@
+//! Day 12: The N-Body Problem.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Moon {
+    pos: [i32; 3],
+    vel: [i32; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sim {
+    moons: Vec<Moon>,
+}
+
+impl Moon {
+    /// Parses a position like "<x=-3, y=10, z=-1>".
+    pub fn parse(line: &str) -> Result<Moon> {
+        let err = || Error::parse(format!("expected <x=.., y=.., z=..>, got {:?}", line));
+        let inner = line
+            .trim()
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .ok_or_else(err)?;
+        let spl: Vec<&str> = inner.split(',').map(str::trim).collect();
+        if spl.len() != 3 {
+            return Err(err());
+        }
+        let mut pos = [0; 3];
+        for ((p, field), name) in pos
+            .iter_mut()
+            .zip(spl.iter())
+            .zip(["x=", "y=", "z="].iter())
+        {
+            *p = field
+                .strip_prefix(name)
+                .and_then(|v| v.trim().parse().ok())
+                .ok_or_else(err)?;
+        }
+        Ok(Moon {
+            pos,
+            vel: [0, 0, 0],
+        })
+    }
+
+    fn apply_acceleration(&mut self, vd: &[i32; 3]) {
+        for (v, d) in self.vel.iter_mut().zip(vd.iter()) {
+            *v += d;
+        }
+    }
+
+    fn get_acceleration_from_gravity(&self, other: &Moon) -> [i32; 3] {
+        let mut out = [0, 0, 0];
+        for (i, o) in out.iter_mut().enumerate() {
+            *o += match (self.pos[i], other.pos[i]) {
+                (x, y) if x < y => 1,
+                (x, y) if x > y => -1,
+                _ => 0,
+            };
+        }
+        out
+    }
+
+    fn apply_velocity(&mut self) {
+        for i in 0..3 {
+            self.pos[i] += self.vel[i];
+        }
+    }
+
+    fn abs_sum(arr: &[i32]) -> i32 {
+        let mut sum = 0;
+        for el in arr.iter() {
+            sum += el.abs();
+        }
+        sum
+    }
+
+    pub fn potential_energy(&self) -> i32 {
+        Moon::abs_sum(&self.pos)
+    }
+
+    pub fn kinetic_energy(&self) -> i32 {
+        Moon::abs_sum(&self.vel)
+    }
+
+    pub fn total_energy(&self) -> i32 {
+        self.potential_energy() * self.kinetic_energy()
+    }
+
+    fn zero_out_dimension(&mut self, i: usize) {
+        self.pos[i] = 0;
+        self.vel[i] = 0;
+    }
+}
+
+impl Sim {
+    pub fn parse(input: &str) -> Result<Sim> {
+        let moons = input::lines(input)
+            .map(|(n, line)| Moon::parse(line).map_err(|e| e.on_line(n)))
+            .collect::<Result<_>>()?;
+        Ok(Sim { moons })
+    }
+
+    fn apply_gravity(&mut self) {
+        for i in 0..self.moons.len() {
+            for j in 0..self.moons.len() {
+                if i == j {
+                    continue;
+                }
+                let a = self.moons[i].get_acceleration_from_gravity(&self.moons[j]);
+                self.moons[i].apply_acceleration(&a);
+            }
+        }
+    }
+
+    fn apply_velocities(&mut self) {
+        for moon in self.moons.iter_mut() {
+            moon.apply_velocity();
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.apply_gravity();
+        self.apply_velocities();
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step()
+        }
+    }
+
+    pub fn total_energy(&self) -> i32 {
+        let mut sum = 0;
+        for moon in self.moons.iter() {
+            sum += moon.total_energy();
+        }
+        sum
+    }
+
+    fn zero_out_dimension(&mut self, i: usize) {
+        for moon in self.moons.iter_mut() {
+            moon.zero_out_dimension(i);
+        }
+    }
+}
+
+pub fn gcd(a: usize, b: usize) -> usize {
+    if a < b {
+        gcd(b, a)
+    } else if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn lcm(a: usize, b: usize) -> usize {
+    (a * b) / gcd(a, b)
+}
+
+pub fn find_period(start: &Sim) -> usize {
+    let mut steps: [usize; 3] = [0, 0, 0];
+    for (dim, dim_steps) in steps.iter_mut().enumerate() {
+        let mut sim = start.clone();
+        for dim2 in 0..3 {
+            if dim == dim2 {
+                continue;
+            }
+            sim.zero_out_dimension(dim2);
+        }
+        let first = sim.clone();
+        for i in 1..usize::MAX {
+            sim.step();
+            if sim == first {
+                *dim_steps = i;
+                break;
+            }
+        }
+    }
+    lcm(steps[0], lcm(steps[1], steps[2]))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Sim;
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn parse(&self, input: &str) -> Result<Sim> {
+        Sim::parse(input)
+    }
+
+    fn part1(&self, sim: &Sim) -> String {
+        let mut sim = sim.clone();
+        sim.run(1000);
+        sim.total_energy().to_string()
+    }
+
+    fn part2(&self, sim: &Sim) -> String {
+        find_period(sim).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(4, 6), 2);
+        assert_eq!(gcd(6, 4), 2);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(6, 8), 24);
+    }
+
+    #[test]
+    fn test_parse() {
+        let sim = Sim::parse("<x=-1, y=0, z=2>\n\n<x=2,y=-10,z=-7>\n").unwrap();
+        assert_eq!(sim.moons[1].pos, [2, -10, -7]);
+        assert_eq!(
+            Sim::parse("<x=1, y=2, z=3>\n<x=1, y=2>")
+                .unwrap_err()
+                .to_string(),
+            "line 2: expected <x=.., y=.., z=..>, got \"<x=1, y=2>\""
+        );
+        assert_eq!(Sim::parse("<x=1, y=2, w=3>").unwrap_err().line(), Some(1));
+    }
+}
@@ -0,0 +1,134 @@
+//! Day 13: Care Package.
+
+use crate::error::Result;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::intcode::{State, StopReason};
+use crate::solution::Solution;
+
+type Pos = Point<i64>;
+
+#[derive(PartialEq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+#[derive(Default)]
+pub struct ScreenState {
+    tiles: Grid<Tile>,
+    pub score: i64,
+    pub paddle: i64,
+    ball: i64,
+}
+
+impl ScreenState {
+    pub fn new() -> Self {
+        Self {
+            tiles: Grid::sparse(),
+            ..Default::default()
+        }
+    }
+
+    fn paint(&mut self, x: i64, y: i64, tile_id: i64) {
+        if x == -1 && y == 0 {
+            self.score = tile_id;
+            return;
+        }
+        let tile = match tile_id {
+            0 => Tile::Empty,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => {
+                self.paddle = x;
+                Tile::Paddle
+            }
+            4 => {
+                self.ball = x;
+                Tile::Ball
+            }
+            _ => panic!("invalid tile_id"),
+        };
+        // if tile == Tile::Paddle {
+        // }
+        self.tiles.set(Pos::new(x, y), tile);
+    }
+
+    /// Renders the screen as text.
+    pub fn render(&self) -> String {
+        self.tiles.render(|c| match c {
+            Some(Tile::Wall) => '#',
+            Some(Tile::Block) => 'x',
+            Some(Tile::Paddle) => '_',
+            Some(Tile::Ball) => 'o',
+            _ => ' ',
+        })
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .fold(0, |cnt, (_, v)| cnt + if *v == Tile::Block { 1 } else { 0 })
+    }
+
+    pub fn run(&mut self, mut state: State) {
+        loop {
+            // state.add_input(self.get_color_as_int(&self.pos));
+            let stop_reason = state.run(false);
+            // assert_eq!(stop_reason, StopReason::Done);
+            let outputs = state.outputs();
+            while !outputs.is_empty() {
+                let x = outputs.pop_front().unwrap();
+                let y = outputs.pop_front().unwrap();
+                let tile_id = outputs.pop_front().unwrap();
+                self.paint(x, y, tile_id);
+            }
+            match stop_reason {
+                StopReason::WaitingOnInput => {
+                    let joystick = if self.ball < self.paddle {
+                        -1
+                    } else if self.ball > self.paddle {
+                        1
+                    } else {
+                        0
+                    };
+                    state.add_input(joystick);
+                }
+                StopReason::Done => break,
+            }
+            // self.print_state();
+        }
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = State;
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn parse(&self, input: &str) -> Result<State> {
+        Ok(State::parse(input)?)
+    }
+
+    fn part1(&self, state: &State) -> String {
+        let mut screen_state = ScreenState::new();
+        screen_state.run(state.clone());
+        screen_state.block_count().to_string()
+    }
+
+    fn part2(&self, state: &State) -> String {
+        let mut state = state.clone();
+        // Insert a quarter.
+        state.write(0, 2);
+        let mut screen_state = ScreenState::new();
+        screen_state.run(state);
+        screen_state.score.to_string()
+    }
+}
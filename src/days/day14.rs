@@ -0,0 +1,269 @@
+//! Day 14: Space Stoichiometry.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+pub struct Chemical {
+    pub name: String,
+    pub qty: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reaction {
+    produced: Chemical,
+    requires: Vec<Chemical>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reactions {
+    reactions: HashMap<String, Reaction>,
+    to_make: HashMap<String, Chemical>,
+    usage: HashMap<String, u64>,
+}
+
+impl Chemical {
+    // input: "2 LGNW"
+    pub fn parse(text: &str) -> Result<Chemical> {
+        let err = || Error::parse(format!("expected quantity and name, got {:?}", text.trim()));
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        if parts.len() != 2 {
+            return Err(err());
+        }
+        let name = parts[1].to_string();
+        let qty = parts[0].parse::<u64>().map_err(|_| err())?;
+        Ok(Chemical { name, qty })
+    }
+}
+
+impl Reaction {
+    /// Parses a line like "7 A, 1 E => 1 FUEL".
+    pub fn parse(line: &str) -> Result<Reaction> {
+        let (requires, produced) = line
+            .split_once("=>")
+            .ok_or_else(|| Error::parse(format!("missing \"=>\" in {:?}", line.trim())))?;
+        let produced = Chemical::parse(produced)?;
+        let requires = requires
+            .split(',')
+            .map(Chemical::parse)
+            .collect::<Result<_>>()?;
+        Ok(Reaction { produced, requires })
+    }
+}
+
+/// Counts, for each chemical, how many of the reactions needed to make `name` use it. `path` is
+/// the chain of chemicals being made, to catch one that is needed to make itself.
+fn count_usage<'a>(
+    name: &'a str,
+    reactions: &'a HashMap<String, Reaction>,
+    done: &mut HashSet<&'a str>,
+    path: &mut Vec<&'a str>,
+    usage: &mut HashMap<String, u64>,
+) -> Result<()> {
+    if name == "ORE" || done.contains(name) {
+        return Ok(());
+    }
+    if path.contains(&name) {
+        return Err(Error::parse(format!("{} is needed to make itself", name)));
+    }
+    path.push(name);
+    for req in reactions[name].requires.iter() {
+        *usage.entry(req.name.clone()).or_insert(0) += 1;
+        count_usage(&req.name, reactions, done, path, usage)?;
+    }
+    path.pop();
+    done.insert(name);
+    Ok(())
+}
+
+impl Reactions {
+    pub fn parse(input: &str) -> Result<Reactions> {
+        let mut reactions: HashMap<String, Reaction> = HashMap::new();
+        for (n, line) in input::lines(input) {
+            let react = Reaction::parse(line).map_err(|e| e.on_line(n))?;
+            let name = &react.produced.name;
+            if reactions.contains_key(name) {
+                return Err(Error::at_line(n, format!("{} is produced twice", name)));
+            }
+            if name == "ORE" {
+                return Err(Error::at_line(n, "ORE can't be produced"));
+            }
+            if react.produced.qty == 0 {
+                return Err(Error::at_line(
+                    n,
+                    format!("reaction for {} makes none", name),
+                ));
+            }
+            reactions.insert(name.clone(), react);
+        }
+        if !reactions.contains_key("FUEL") {
+            return Err(Error::parse("no reaction produces FUEL"));
+        }
+        let mut missing: Vec<&String> = reactions
+            .values()
+            .flat_map(|r| r.requires.iter().map(|c| &c.name))
+            .filter(|name| *name != "ORE" && !reactions.contains_key(*name))
+            .collect();
+        missing.sort();
+        if let Some(name) = missing.first() {
+            return Err(Error::parse(format!("no reaction produces {}", name)));
+        }
+        let mut usage = HashMap::new();
+        count_usage(
+            "FUEL",
+            &reactions,
+            &mut HashSet::new(),
+            &mut Vec::new(),
+            &mut usage,
+        )?;
+        usage.insert("FUEL".to_string(), 0);
+        Ok(Reactions {
+            reactions,
+            to_make: HashMap::new(),
+            usage,
+        })
+    }
+
+    fn find_zero_count_thing_to_make(&mut self) -> Chemical {
+        let name = self
+            .to_make
+            .values()
+            .find_map(|v| {
+                if *self.usage.get(&v.name).unwrap() == 0 {
+                    Some(v.name.clone())
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        self.to_make.remove(&name).unwrap()
+    }
+
+    pub fn produce(&mut self, chem: &Chemical) -> u64 {
+        self.to_make.insert(chem.name.to_string(), chem.clone());
+
+        while !self.to_make.is_empty() {
+            // dbg!(&self.reactions);
+            // dbg!(&self.to_make);
+            // go through the "to_make" list and find a thing with count=0
+            let next_thing = self.find_zero_count_thing_to_make();
+            // dbg!(&next_thing);
+
+            if next_thing.name == "ORE" {
+                // dbg!(&next_thing);
+                assert!(self.to_make.is_empty());
+                return next_thing.qty;
+            }
+
+            let reaction = self.reactions.remove(&next_thing.name).unwrap();
+
+            let need = next_thing.qty;
+            let produced = reaction.produced.qty;
+            let times = need.div_ceil(produced);
+
+            for req in reaction.requires.iter() {
+                *self.usage.get_mut(&req.name).unwrap() -= 1;
+                let added_qty = req.qty * times;
+                self.to_make
+                    .entry(req.name.clone())
+                    .and_modify(|chem| chem.qty += added_qty)
+                    .or_insert(Chemical {
+                        name: req.name.clone(),
+                        qty: added_qty,
+                    });
+            }
+        }
+        panic!("nope");
+    }
+}
+
+/// Ore needed to make given amount of fuel.
+pub fn ore_for_fuel(reactions: &Reactions, fuel: u64) -> u64 {
+    reactions.clone().produce(&Chemical {
+        name: "FUEL".to_string(),
+        qty: fuel,
+    })
+}
+
+/// Most fuel that can be made from given amount of ore.
+pub fn max_fuel(reactions: &Reactions, ore: u64) -> u64 {
+    // Binary search: lo can always be made, hi never can.
+    let mut lo = 0;
+    let mut hi = 1;
+    while ore_for_fuel(reactions, hi) <= ore {
+        lo = hi;
+        hi *= 2;
+    }
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if ore_for_fuel(reactions, mid) <= ore {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Reactions;
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn parse(&self, input: &str) -> Result<Reactions> {
+        Reactions::parse(input)
+    }
+
+    fn part1(&self, reactions: &Reactions) -> String {
+        ore_for_fuel(reactions, 1).to_string()
+    }
+
+    fn part2(&self, reactions: &Reactions) -> String {
+        max_fuel(reactions, 1_000_000_000_000).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let reactions = Reactions::parse("10 ORE => 10 A\n1 ORE => 1 B\n7 A, 1 B => 1 FUEL\n");
+        assert_eq!(reactions.unwrap().reactions.len(), 3);
+
+        let err = |input: &str| Reactions::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            err("1 ORE => 1 FUEL\n1 ORE 1 A"),
+            "line 2: missing \"=>\" in \"1 ORE 1 A\""
+        );
+        assert_eq!(
+            err("1 ORE => 1 FUEL\nx ORE => 1 A"),
+            "line 2: expected quantity and name, got \"x ORE\""
+        );
+        assert_eq!(
+            err("1 ORE => 1 FUEL\n2 ORE => 1 FUEL"),
+            "line 2: FUEL is produced twice"
+        );
+        assert_eq!(err("1 ORE => 1 A"), "no reaction produces FUEL");
+        assert_eq!(err("1 A => 1 FUEL"), "no reaction produces A");
+        assert_eq!(
+            err("1 A => 1 FUEL\n1 B => 1 A\n1 A, 1 ORE => 1 B"),
+            "A is needed to make itself"
+        );
+        assert_eq!(err("1 FUEL => 1 ORE"), "line 1: ORE can't be produced");
+        assert_eq!(
+            err("1 ORE => 1 FUEL\n1 ORE => 0 A"),
+            "line 2: reaction for A makes none"
+        );
+        // Reactions that FUEL doesn't need are left alone.
+        let extra = Reactions::parse("2 ORE => 1 FUEL\n1 ORE, 1 FUEL => 1 A").unwrap();
+        assert_eq!(ore_for_fuel(&extra, 3), 6);
+    }
+}
@@ -0,0 +1,63 @@
+//! Day 2: 1202 Program Alarm.
+
+use crate::combinatorics::product;
+use crate::error::Result;
+use crate::intcode::State;
+use crate::solution::Solution;
+
+pub struct Day2;
+
+/// Runs the program with given noun and verb, returns the value left at address 0.
+pub fn run_with(state: &State, noun: i64, verb: i64) -> i64 {
+    let mut state = state.clone();
+    state.write(1, noun);
+    state.write(2, verb);
+    state.run(false);
+    state.read(0)
+}
+
+/// "Find the input noun and verb that cause the program to produce the output 19690720. What is
+/// 100 * noun + verb?"
+pub fn find_noun_verb(state: &State, target: i64) -> Option<i64> {
+    let values: Vec<i64> = (0..=99).collect();
+    product(&[&values, &values])
+        .find(|pick| run_with(state, pick[0], pick[1]) == target)
+        .map(|pick| 100 * pick[0] + pick[1])
+}
+
+impl Solution for Day2 {
+    type Input = State;
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn default_input(&self) -> String {
+        "inputs/day2_input.txt".to_string()
+    }
+
+    fn parse(&self, input: &str) -> Result<State> {
+        Ok(State::parse(input)?)
+    }
+
+    fn part1(&self, state: &State) -> String {
+        run_with(state, 12, 2).to_string()
+    }
+
+    fn part2(&self, state: &State) -> String {
+        find_noun_verb(state, 19690720)
+            .expect("could not find answer")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day2_part2() {
+        let state = State::from_file("inputs/day2_input.txt");
+        assert_eq!(find_noun_verb(&state, 19690720), Some(9425));
+    }
+}
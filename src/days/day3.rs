@@ -0,0 +1,47 @@
+//! Day 3: Crossed Wires.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::Solution;
+use crate::wires::wire_set::WireSet;
+use crate::wires::Path;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = WireSet;
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn default_input(&self) -> String {
+        "inputs/day3_1.txt".to_string()
+    }
+
+    fn parse(&self, input: &str) -> Result<WireSet> {
+        let mut set = WireSet::new();
+        for (line, text) in input::lines(input) {
+            let path = Path::parse(text).map_err(|source| Error::Path { line, source })?;
+            set.add_path(&path);
+        }
+        if set.len() < 2 {
+            return Err(Error::parse("expected at least two wires"));
+        }
+        Ok(set)
+    }
+
+    fn part1(&self, set: &WireSet) -> String {
+        set.closest_crossing()
+            .expect("wires don't cross")
+            .distance()
+            .to_string()
+    }
+
+    fn part2(&self, set: &WireSet) -> String {
+        set.fewest_steps_crossing()
+            .expect("wires don't cross")
+            .combined_steps()
+            .to_string()
+    }
+}
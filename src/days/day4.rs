@@ -0,0 +1,50 @@
+//! Day 4: Secure Container.
+
+use crate::error::Result;
+use crate::password::{self, RuleSet};
+use crate::solution::Solution;
+
+// Rules:
+// * It is a six-digit number.
+// * The value is within the range given in your puzzle input.
+// * Two adjacent digits are the same (like 22 in 122345).
+// * Going from left to right, the digits never decrease; they only ever increase or stay the same
+//   (like 111123 or 135679).
+//
+// Part 2 adds: the two adjacent matching digits are not part of a larger group of matching
+// digits. Both are presets in `password::RuleSet`.
+
+pub fn part1(from: u64, to: u64) -> u64 {
+    count(&RuleSet::part1(from, to))
+}
+
+pub fn part2(from: u64, to: u64) -> u64 {
+    count(&RuleSet::part2(from, to))
+}
+
+fn count(rules: &RuleSet) -> u64 {
+    rules.count().expect("presets have a range")
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = (u64, u64);
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    /// Parses puzzle input, e.g., "109165-576723".
+    fn parse(&self, input: &str) -> Result<(u64, u64)> {
+        password::parse_range(input)
+    }
+
+    fn part1(&self, &(from, to): &(u64, u64)) -> String {
+        part1(from, to).to_string()
+    }
+
+    fn part2(&self, &(from, to): &(u64, u64)) -> String {
+        part2(from, to).to_string()
+    }
+}
@@ -0,0 +1,43 @@
+//! Day 5: Sunny with a Chance of Asteroids.
+
+use crate::error::Result;
+use crate::intcode::State;
+use crate::solution::Solution;
+
+pub struct Day5;
+
+/// Runs the diagnostic program with given system ID, returns the diagnostic code: the last
+/// output. Earlier outputs are test results and must all be 0.
+pub fn diagnostic_code(state: &State, system_id: i64) -> i64 {
+    let mut state = state.clone();
+    state.add_input(system_id);
+    state.run(false);
+    let outputs = state.outputs();
+    let code = outputs.pop_back().expect("no output");
+    assert!(
+        outputs.iter().all(|x| *x == 0),
+        "failed tests: {:?}",
+        outputs
+    );
+    code
+}
+
+impl Solution for Day5 {
+    type Input = State;
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn parse(&self, input: &str) -> Result<State> {
+        Ok(State::parse(input)?)
+    }
+
+    fn part1(&self, state: &State) -> String {
+        diagnostic_code(state, 1).to_string()
+    }
+
+    fn part2(&self, state: &State) -> String {
+        diagnostic_code(state, 5).to_string()
+    }
+}
@@ -0,0 +1,141 @@
+//! Day 6: Universal Orbit Map.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::Solution;
+use crate::tree::validate::{self, Report};
+use crate::tree::Tree;
+use std::collections::HashSet;
+
+/// (line number, orbited, orbiting) for each line.
+fn parse_edges(input: &str) -> Result<Vec<(usize, &str, &str)>> {
+    input::lines(input)
+        .map(|(n, line)| match line.split_once(')') {
+            Some((lhs, rhs)) if !lhs.is_empty() && !rhs.is_empty() && !rhs.contains(')') => {
+                Ok((n, lhs, rhs))
+            }
+            _ => Err(Error::at_line(n, format!("invalid orbit {:?}", line))),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Orbits {
+    tree: Tree,
+}
+
+impl Orbits {
+    /// Parses lines like "COM)B": B orbits COM. Names can be any length, but not contain ')'.
+    /// Several separate systems are fine, but an object can't orbit two others, or itself.
+    pub fn parse(input: &str) -> Result<Orbits> {
+        let edges = parse_edges(input)?;
+        let mut children = HashSet::new();
+        for (n, _, rhs) in edges.iter() {
+            if !children.insert(rhs) {
+                return Err(Error::at_line(*n, format!("{} orbits two objects", rhs)));
+            }
+        }
+        let tree = Tree::from_edges(edges.iter().map(|(_, lhs, rhs)| (*lhs, *rhs)))
+            .map_err(|e| Error::parse(e.to_string()))?;
+        Ok(Orbits { tree })
+    }
+
+    /// Checks an orbit map for problems that `parse` would reject, and reports them all along
+    /// with the roots and separate systems. Only fails on lines that aren't orbits.
+    pub fn validate(input: &str) -> Result<Report> {
+        let edges = parse_edges(input)?;
+        Ok(validate::validate(
+            edges.iter().map(|(_, lhs, rhs)| (*lhs, *rhs)),
+        ))
+    }
+
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    /// Total number of direct and indirect orbits.
+    pub fn count_orbits(&self) -> usize {
+        self.tree.total_depth()
+    }
+
+    /// Orbital transfers needed to get from the object `from` orbits to the object `to` orbits.
+    /// None if either is unknown or orbits nothing, or they're in separate systems.
+    pub fn transfers(&self, from: &str, to: &str) -> Option<usize> {
+        let from = self.tree.parent(self.tree.id(from)?)?;
+        let to = self.tree.parent(self.tree.id(to)?)?;
+        self.tree.distance(from, to)
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Orbits;
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn parse(&self, input: &str) -> Result<Orbits> {
+        Orbits::parse(input)
+    }
+
+    fn part1(&self, orbits: &Orbits) -> String {
+        orbits.count_orbits().to_string()
+    }
+
+    fn part2(&self, orbits: &Orbits) -> String {
+        orbits
+            .transfers("YOU", "SAN")
+            .expect("YOU and SAN aren't in the same system")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let orbits = Orbits::parse("COM)B\nB)C\nC)D\n").unwrap();
+        assert_eq!(orbits.count_orbits(), 6);
+        assert_eq!(
+            Orbits::parse("COM)B\nB-C").unwrap_err().to_string(),
+            "line 2: invalid orbit \"B-C\""
+        );
+        assert_eq!(
+            Orbits::parse("COM)B\nA)B").unwrap_err().to_string(),
+            "line 2: B orbits two objects"
+        );
+        assert_eq!(
+            Orbits::parse("A)B\nB)A").unwrap_err().to_string(),
+            "cycle: A -> B"
+        );
+    }
+
+    #[test]
+    fn test_transfers() {
+        let orbits =
+            Orbits::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN")
+                .unwrap();
+        assert_eq!(orbits.transfers("YOU", "SAN"), Some(4));
+        assert_eq!(orbits.transfers("YOU", "COM"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let report = Orbits::validate("Sun)Earth\nEarth)Moon\nX)Y\nY)X\nSun)Moon").unwrap();
+        assert_eq!(report.roots, vec!["Sun"]);
+        assert_eq!(report.duplicate_parents[0].parents, vec!["Earth", "Sun"]);
+        assert_eq!(report.cycles, vec![vec!["X", "Y"]]);
+        assert_eq!(report.components.len(), 2);
+        assert_eq!(
+            Orbits::validate("A)B\nA)B)C").unwrap_err().to_string(),
+            "line 2: invalid orbit \"A)B)C\""
+        );
+        // Long names and separate systems are fine.
+        let orbits = Orbits::parse("Sun)Earth\nEarth)YOU\nSun)Mars\nMars)SAN\nX)Y").unwrap();
+        assert_eq!(orbits.transfers("YOU", "SAN"), Some(2));
+    }
+}
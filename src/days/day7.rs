@@ -0,0 +1,219 @@
+//! Day 7: Amplification Circuit.
+
+use crate::combinatorics::{combinations, permutations};
+use crate::error::{Error, Result};
+use crate::intcode::{State, StopReason};
+use crate::solution::Solution;
+
+/// How amplifier outputs are wired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// Each amplifier feeds the next; the last one's output is the result.
+    Chain,
+    /// Like `Chain`, but the last amplifier also feeds the first, until the last one halts.
+    Ring,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmpConfig {
+    pub count: usize,
+    pub topology: Topology,
+    /// Input to the first amplifier, after its phase.
+    pub initial_signal: i64,
+    /// Phases to choose from, each used by at most one amplifier. If there are more phases than
+    /// amplifiers, every subset of the right size is tried.
+    pub phases: Vec<i64>,
+}
+
+impl AmpConfig {
+    /// Five amplifiers in a chain, phases 0 to 4.
+    pub fn part1() -> AmpConfig {
+        AmpConfig {
+            count: 5,
+            topology: Topology::Chain,
+            initial_signal: 0,
+            phases: (0..5).collect(),
+        }
+    }
+
+    /// Five amplifiers in a feedback ring, phases 5 to 9.
+    pub fn part2() -> AmpConfig {
+        AmpConfig {
+            count: 5,
+            topology: Topology::Ring,
+            initial_signal: 0,
+            phases: (5..10).collect(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.count == 0 {
+            return Err(Error::parse("need at least one amplifier"));
+        }
+        if self.phases.len() < self.count {
+            return Err(Error::parse(format!(
+                "only {} phases for {} amplifiers",
+                self.phases.len(),
+                self.count
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Runs the amplifiers with one phase each, returns the last output of the last amplifier. None
+/// if it never outputs anything, or if a ring gets stuck with every amplifier waiting for
+/// input.
+pub fn run(base: &State, topology: Topology, initial_signal: i64, phases: &[i64]) -> Option<i64> {
+    let count = phases.len();
+    let mut states: Vec<State> = phases
+        .iter()
+        .map(|phase| {
+            let mut state = base.clone();
+            state.add_input(*phase);
+            state
+        })
+        .collect();
+    states.first_mut()?.add_input(initial_signal);
+    let mut signal = None;
+    let mut to_run = 0;
+    // Amplifiers run since one last produced output, to spot a stuck ring.
+    let mut idle = 0;
+    loop {
+        let stop_reason = states[to_run].run(false);
+        let outputs: Vec<i64> = states[to_run].outputs().drain(..).collect();
+        idle = if outputs.is_empty() { idle + 1 } else { 0 };
+        let last = to_run == count - 1;
+        if last {
+            signal = outputs.last().copied().or(signal);
+            if stop_reason == StopReason::Done || topology == Topology::Chain {
+                return signal;
+            }
+        }
+        if idle > count {
+            return None;
+        }
+        let next = (to_run + 1) % count;
+        for value in outputs {
+            states[next].add_input(value);
+        }
+        to_run = next;
+    }
+}
+
+/// Highest signal found, and the phases that produce it, in amplifier order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Best {
+    pub signal: i64,
+    pub phases: Vec<i64>,
+}
+
+/// Tries every assignment of phases to amplifiers, returns the one with the highest signal.
+/// None if no assignment produces a signal.
+pub fn best_signal(base: &State, config: &AmpConfig) -> Result<Option<Best>> {
+    config.validate()?;
+    Ok(combinations(&config.phases, config.count)
+        .flat_map(|chosen| permutations(&chosen))
+        .filter_map(|phases| {
+            run(base, config.topology, config.initial_signal, &phases)
+                .map(|signal| Best { signal, phases })
+        })
+        .max_by_key(|best| best.signal))
+}
+
+fn solve(state: &State, config: &AmpConfig) -> String {
+    best_signal(state, config)
+        .expect("presets are valid")
+        .expect("no phases produce a signal")
+        .signal
+        .to_string()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = State;
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn parse(&self, input: &str) -> Result<State> {
+        Ok(State::parse(input)?)
+    }
+
+    fn part1(&self, state: &State) -> String {
+        solve(state, &AmpConfig::part1())
+    }
+
+    fn part2(&self, state: &State) -> String {
+        solve(state, &AmpConfig::part2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(n: usize) -> State {
+        State::from_file(&format!("inputs/day7_test{}.txt", n))
+    }
+
+    #[test]
+    fn test_examples() {
+        let best = |n, config: &AmpConfig| best_signal(&example(n), config).unwrap().unwrap();
+        let part1 = AmpConfig::part1();
+        assert_eq!(
+            best(1, &part1),
+            Best {
+                signal: 43210,
+                phases: vec![4, 3, 2, 1, 0]
+            }
+        );
+        assert_eq!(best(2, &part1).signal, 54321);
+        assert_eq!(best(3, &part1).phases, vec![1, 0, 4, 3, 2]);
+        let part2 = AmpConfig::part2();
+        assert_eq!(
+            best(4, &part2),
+            Best {
+                signal: 139629729,
+                phases: vec![9, 8, 7, 6, 5]
+            }
+        );
+        assert_eq!(best(5, &part2).signal, 18216);
+    }
+
+    #[test]
+    fn test_config() {
+        // Fewer amplifiers, and a different initial signal.
+        let config = AmpConfig {
+            count: 2,
+            topology: Topology::Chain,
+            initial_signal: 100,
+            phases: vec![3, 4],
+        };
+        // Example 1 outputs 10 * input + phase.
+        let state = example(1);
+        assert_eq!(run(&state, Topology::Chain, 100, &[3, 4]), Some(10034));
+        assert!(best_signal(&state, &config).unwrap().is_some());
+
+        // Any 2 of 3 phases; 4 then 3 gives 10043.
+        let choose = AmpConfig {
+            phases: vec![3, 1, 4],
+            ..config.clone()
+        };
+        assert_eq!(
+            best_signal(&state, &choose).unwrap(),
+            Some(Best {
+                signal: 10043,
+                phases: vec![4, 3]
+            })
+        );
+
+        let bad = AmpConfig { count: 3, ..config };
+        assert_eq!(
+            best_signal(&state, &bad).unwrap_err().to_string(),
+            "only 2 phases for 3 amplifiers"
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Day 8: Space Image Format. The decoder lives in `sif`.
+
+use crate::error::Result;
+use crate::sif::Image;
+use crate::solution::Solution;
+
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Image;
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn parse(&self, input: &str) -> Result<Image> {
+        Image::parse(input, WIDTH, HEIGHT)
+    }
+
+    fn part1(&self, img: &Image) -> String {
+        img.checksum().to_string()
+    }
+
+    fn part2(&self, img: &Image) -> String {
+        img.merged().render()
+    }
+}
@@ -0,0 +1,37 @@
+//! Day 9: Sensor Boost.
+
+use crate::error::Result;
+use crate::intcode::State;
+use crate::solution::Solution;
+
+pub struct Day9;
+
+/// Runs the BOOST program with one input, returns its single output.
+pub fn boost(state: &State, input: i64) -> i64 {
+    let mut state = state.clone();
+    state.add_input(input);
+    state.run(false);
+    let outputs = state.outputs();
+    assert_eq!(outputs.len(), 1, "malfunctioning opcodes: {:?}", outputs);
+    outputs.pop_front().unwrap()
+}
+
+impl Solution for Day9 {
+    type Input = State;
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn parse(&self, input: &str) -> Result<State> {
+        Ok(State::parse(input)?)
+    }
+
+    fn part1(&self, state: &State) -> String {
+        boost(state, 1).to_string()
+    }
+
+    fn part2(&self, state: &State) -> String {
+        boost(state, 2).to_string()
+    }
+}
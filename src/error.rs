@@ -0,0 +1,156 @@
+//! Error type shared by the puzzle parsers and input loading.
+
+use crate::intcode::ProgramError;
+use crate::wires::PathError;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Input could not be read.
+    Io { path: String, source: io::Error },
+    /// Input text is malformed. Lines count from 1; `None` if the error isn't tied to one line.
+    Parse {
+        line: Option<usize>,
+        message: String,
+    },
+    /// Intcode program text is malformed.
+    Program(ProgramError),
+    /// Wire path on the given line is malformed.
+    Path { line: usize, source: PathError },
+    /// Input is well formed, but asks for more than can be worked out, e.g. an answer too big
+    /// for its type.
+    Limit(String),
+    /// Another error, in the named input.
+    InFile { path: String, source: Box<Error> },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Parse error not tied to a line.
+    pub fn parse<S: Into<String>>(message: S) -> Error {
+        Error::Parse {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// Parse error on the given line, counting from 1.
+    pub fn at_line<S: Into<String>>(line: usize, message: S) -> Error {
+        Error::Parse {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    /// Input asks for more than can be worked out.
+    pub fn limit<S: Into<String>>(message: S) -> Error {
+        Error::Limit(message.into())
+    }
+
+    /// Puts a parse error on the given line, if it isn't on one already.
+    pub fn on_line(self, line: usize) -> Error {
+        match self {
+            Error::Parse {
+                line: None,
+                message,
+            } => Error::Parse {
+                line: Some(line),
+                message,
+            },
+            _ => self,
+        }
+    }
+
+    /// Adds the name of the input the error came from. I/O errors already have it.
+    pub fn in_file(self, path: &str) -> Error {
+        match self {
+            Error::Io { .. } | Error::InFile { .. } => self,
+            _ => Error::InFile {
+                path: path.to_string(),
+                source: Box::new(self),
+            },
+        }
+    }
+
+    /// Line the error is on, if known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Error::Io { .. } | Error::Limit(_) => None,
+            Error::Parse { line, .. } => *line,
+            Error::Program(e) => Some(e.line),
+            Error::Path { line, .. } => Some(*line),
+            Error::InFile { source, .. } => source.line(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {}", path, source),
+            Error::Parse {
+                line: Some(line),
+                message,
+            } => write!(f, "line {}: {}", line, message),
+            Error::Parse {
+                line: None,
+                message,
+            } => write!(f, "{}", message),
+            Error::Program(e) => write!(f, "{}", e),
+            Error::Limit(message) => write!(f, "{}", message),
+            Error::Path { line, source } => write!(f, "line {}: {}", line, source),
+            Error::InFile { path, source } => write!(f, "{}: {}", path, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Program(e) => Some(e),
+            Error::Path { source, .. } => Some(source),
+            Error::InFile { source, .. } => Some(source.as_ref()),
+            Error::Parse { .. } | Error::Limit(_) => None,
+        }
+    }
+}
+
+impl From<ProgramError> for Error {
+    fn from(e: ProgramError) -> Error {
+        Error::Program(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::parse_program;
+
+    #[test]
+    fn test_display() {
+        let e = Error::at_line(3, "invalid orbit \"AB\"").in_file("inputs/day6.txt");
+        assert_eq!(
+            e.to_string(),
+            "inputs/day6.txt: line 3: invalid orbit \"AB\""
+        );
+        assert_eq!(e.line(), Some(3));
+        assert_eq!(Error::parse("empty image").line(), None);
+        assert_eq!(Error::parse("bad").on_line(4).to_string(), "line 4: bad");
+        assert_eq!(Error::at_line(2, "bad").on_line(4).line(), Some(2));
+        let e = Error::limit("too big").on_line(4).in_file("x.txt");
+        assert_eq!(e.to_string(), "x.txt: too big");
+        assert_eq!(e.line(), None);
+
+        let e = Error::from(parse_program("1,\n2,x").unwrap_err()).in_file("prog.txt");
+        assert_eq!(
+            e.to_string(),
+            "prog.txt: token 2 (line 2, column 3, byte 5): invalid value \"x\""
+        );
+        assert_eq!(e.line(), Some(2));
+        // Naming the file twice doesn't nest.
+        assert_eq!(e.in_file("other.txt").to_string().matches(": ").count(), 2);
+    }
+}
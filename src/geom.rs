@@ -0,0 +1,318 @@
+//! 2D geometry shared between puzzles: points, directions and bounding boxes.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Integer type usable as a point coordinate.
+pub trait Coord:
+    Copy
+    + Debug
+    + Default
+    + Hash
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i32, i64, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    pub fn origin() -> Self {
+        Point::new(T::ZERO, T::ZERO)
+    }
+
+    /// Manhattan (taxicab) distance to other.
+    pub fn manhattan(&self, other: &Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Chebyshev (chessboard) distance to other.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Point one step away in the given direction.
+    pub fn step(&self, dir: Dir) -> Self {
+        *self + dir.delta()
+    }
+
+    /// The four points sharing an edge with this one, in `Dir::ALL` order.
+    pub fn neighbours4(&self) -> [Self; 4] {
+        Dir::ALL.map(|dir| self.step(dir))
+    }
+
+    /// The eight points sharing an edge or a corner with this one, clockwise starting from up.
+    pub fn neighbours8(&self) -> [Self; 8] {
+        let (o, i) = (T::ZERO, T::ONE);
+        [
+            (o, -i),
+            (i, -i),
+            (i, o),
+            (i, i),
+            (o, i),
+            (-i, i),
+            (-i, o),
+            (-i, -i),
+        ]
+        .map(|(dx, dy)| Point::new(self.x + dx, self.y + dy))
+    }
+}
+
+impl<T: Coord> Add for Point<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> Sub for Point<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Neg for Point<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// Scales both coordinates.
+impl<T: Coord> Mul<T> for Point<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// Direction on a grid. Uses screen coordinates: y grows downwards, so up is -y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    U,
+    R,
+    D,
+    L,
+}
+
+impl Dir {
+    /// All directions, clockwise starting from up.
+    pub const ALL: [Dir; 4] = [Dir::U, Dir::R, Dir::D, Dir::L];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::U => Self::R,
+            Self::R => Self::D,
+            Self::D => Self::L,
+            Self::L => Self::U,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::U => Self::L,
+            Self::L => Self::D,
+            Self::D => Self::R,
+            Self::R => Self::U,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::U => Self::D,
+            Self::D => Self::U,
+            Self::L => Self::R,
+            Self::R => Self::L,
+        }
+    }
+
+    /// Change in position for one step in this direction.
+    pub fn delta<T: Coord>(self) -> Point<T> {
+        let (o, i) = (T::ZERO, T::ONE);
+        match self {
+            Self::U => Point::new(o, -i),
+            Self::R => Point::new(i, o),
+            Self::D => Point::new(o, i),
+            Self::L => Point::new(-i, o),
+        }
+    }
+}
+
+/// Smallest rectangle containing a set of points. Both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Coord> BoundingBox<T> {
+    /// Box containing just the given point.
+    pub fn new(p: Point<T>) -> Self {
+        BoundingBox { min: p, max: p }
+    }
+
+    /// Box containing all given points, or None if there are none.
+    pub fn from_points<I: IntoIterator<Item = Point<T>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let mut bb = BoundingBox::new(points.next()?);
+        for p in points {
+            bb.include(p);
+        }
+        Some(bb)
+    }
+
+    /// Grows the box to contain p.
+    pub fn include(&mut self, p: Point<T>) {
+        self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Number of columns covered.
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    /// Number of rows covered.
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// All points inside the box, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+        let bb = *self;
+        let mut next = Some(bb.min);
+        std::iter::from_fn(move || {
+            let p = next?;
+            next = if p.x < bb.max.x {
+                Some(Point::new(p.x + T::ONE, p.y))
+            } else if p.y < bb.max.y {
+                Some(Point::new(bb.min.x, p.y + T::ONE))
+            } else {
+                None
+            };
+            Some(p)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a + b, Point::new(-2, 7));
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(b * 2, Point::new(-6, 10));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a: Point<i64> = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.manhattan(&Point::origin()), 3);
+    }
+
+    #[test]
+    fn test_dir() {
+        for dir in Dir::ALL.iter() {
+            assert_eq!(dir.turn_right().turn_left(), *dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.delta::<i32>() + dir.reverse().delta(), Point::origin());
+        }
+        assert_eq!(Dir::U.delta::<i32>(), Point::new(0, -1));
+        assert_eq!(Point::new(3, 3).step(Dir::L), Point::new(2, 3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point::new(0, 0);
+        assert_eq!(
+            p.neighbours4(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        let n8 = p.neighbours8();
+        assert!(n8.iter().all(|n| n.chebyshev(&p) == 1));
+        assert!(p.neighbours4().iter().all(|n| n8.contains(n)));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        assert_eq!(BoundingBox::<i32>::from_points(vec![]), None);
+        let bb = BoundingBox::from_points(vec![Point::new(2, -1), Point::new(0, 1)]).unwrap();
+        assert_eq!(bb.min, Point::new(0, -1));
+        assert_eq!(bb.max, Point::new(2, 1));
+        assert_eq!((bb.width(), bb.height()), (3, 3));
+        assert!(bb.contains(&Point::new(1, 0)));
+        assert!(!bb.contains(&Point::new(3, 0)));
+        let points: Vec<Point<i32>> = bb.points().collect();
+        assert_eq!(points.len(), 9);
+        assert_eq!(points[0], bb.min);
+        assert_eq!(points[1], Point::new(1, -1));
+        assert_eq!(points[8], bb.max);
+    }
+}
@@ -0,0 +1,321 @@
+//! Grid of cells addressed by `Point<i64>`, with sparse and dense storage.
+//!
+//! Sparse grids keep cells in a hash map and can grow in any direction, which suits robots
+//! painting an unbounded area. Dense grids keep a fixed width x height area starting at (0, 0)
+//! in a vector, which suits maps parsed from text.
+
+use crate::geom::{BoundingBox, Point};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+pub type Pos = Point<i64>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Cells<T> {
+    Sparse(HashMap<Pos, T>),
+    Dense {
+        width: usize,
+        height: usize,
+        cells: Vec<Option<T>>,
+    },
+}
+
+/// Grid where every cell is either set to a value or empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    cells: Cells<T>,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Grid::sparse()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Empty grid with hash map storage.
+    pub fn sparse() -> Self {
+        Grid {
+            cells: Cells::Sparse(HashMap::new()),
+        }
+    }
+
+    /// Empty grid with vector storage, covering x in 0..width and y in 0..height.
+    pub fn dense(width: usize, height: usize) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        cells.resize_with(width * height, || None);
+        Grid {
+            cells: Cells::Dense {
+                width,
+                height,
+                cells,
+            },
+        }
+    }
+
+    /// Parses a character map into a dense grid, one line per row. `f` maps each character to
+    /// a cell value, or None to leave the cell empty. Short lines leave the rest of the row empty.
+    pub fn parse_dense<F: FnMut(char) -> Option<T>>(text: &str, f: F) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut grid = Grid::dense(width, lines.len());
+        grid.fill_from(&lines, f);
+        grid
+    }
+
+    /// Like `parse_dense`, but stores the cells in a sparse grid.
+    pub fn parse_sparse<F: FnMut(char) -> Option<T>>(text: &str, f: F) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let mut grid = Grid::sparse();
+        grid.fill_from(&lines, f);
+        grid
+    }
+
+    fn fill_from<F: FnMut(char) -> Option<T>>(&mut self, lines: &[&str], mut f: F) {
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = f(c) {
+                    self.set(Pos::new(x as i64, y as i64), value);
+                }
+            }
+        }
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.cells, Cells::Sparse(_))
+    }
+
+    /// Index into dense storage, None if out of range.
+    fn index(width: usize, height: usize, p: &Pos) -> Option<usize> {
+        if p.x < 0 || p.y < 0 || p.x as usize >= width || p.y as usize >= height {
+            None
+        } else {
+            Some(p.y as usize * width + p.x as usize)
+        }
+    }
+
+    pub fn get(&self, p: &Pos) -> Option<&T> {
+        match &self.cells {
+            Cells::Sparse(map) => map.get(p),
+            Cells::Dense {
+                width,
+                height,
+                cells,
+            } => Grid::<T>::index(*width, *height, p).and_then(|i| cells[i].as_ref()),
+        }
+    }
+
+    pub fn get_mut(&mut self, p: &Pos) -> Option<&mut T> {
+        match &mut self.cells {
+            Cells::Sparse(map) => map.get_mut(p),
+            Cells::Dense {
+                width,
+                height,
+                cells,
+            } => Grid::<T>::index(*width, *height, p).and_then(move |i| cells[i].as_mut()),
+        }
+    }
+
+    /// Sets a cell, returns the previous value. Panics if p is outside a dense grid.
+    pub fn set(&mut self, p: Pos, value: T) -> Option<T> {
+        match &mut self.cells {
+            Cells::Sparse(map) => map.insert(p, value),
+            Cells::Dense {
+                width,
+                height,
+                cells,
+            } => {
+                let i = Grid::<T>::index(*width, *height, &p)
+                    .unwrap_or_else(|| panic!("{:?} outside {}x{} grid", p, width, height));
+                cells[i].replace(value)
+            }
+        }
+    }
+
+    /// Empties a cell, returns the previous value.
+    pub fn remove(&mut self, p: &Pos) -> Option<T> {
+        match &mut self.cells {
+            Cells::Sparse(map) => map.remove(p),
+            Cells::Dense {
+                width,
+                height,
+                cells,
+            } => Grid::<T>::index(*width, *height, p).and_then(|i| cells[i].take()),
+        }
+    }
+
+    /// Iterates over set cells. Dense grids go row by row, sparse grids in no particular order.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Pos, &T)> + '_> {
+        match &self.cells {
+            Cells::Sparse(map) => Box::new(map.iter().map(|(p, v)| (*p, v))),
+            Cells::Dense { width, cells, .. } => {
+                let width = *width;
+                Box::new(cells.iter().enumerate().filter_map(move |(i, v)| {
+                    v.as_ref()
+                        .map(|v| (Pos::new((i % width) as i64, (i / width) as i64), v))
+                }))
+            }
+        }
+    }
+
+    /// Number of set cells.
+    pub fn len(&self) -> usize {
+        match &self.cells {
+            Cells::Sparse(map) => map.len(),
+            Cells::Dense { cells, .. } => cells.iter().filter(|v| v.is_some()).count(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Smallest box containing all set cells, None if there are none.
+    pub fn bounds(&self) -> Option<BoundingBox<i64>> {
+        BoundingBox::from_points(self.iter().map(|(p, _)| p))
+    }
+
+    /// Area covered by the grid: the whole of a dense grid, or the bounds of a sparse one.
+    pub fn area(&self) -> Option<BoundingBox<i64>> {
+        match &self.cells {
+            Cells::Sparse(_) => self.bounds(),
+            Cells::Dense { width, height, .. } => {
+                if *width == 0 || *height == 0 {
+                    None
+                } else {
+                    Some(BoundingBox {
+                        min: Pos::origin(),
+                        max: Pos::new(*width as i64 - 1, *height as i64 - 1),
+                    })
+                }
+            }
+        }
+    }
+
+    /// x and y ranges of the grid's area, both empty if it has none. The area of a sparse grid
+    /// takes a scan of every cell, so work it out once and pass the ranges around.
+    fn spans(&self) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
+        let (min, max) = self
+            .area()
+            .map_or((Pos::new(0, 0), Pos::new(-1, -1)), |bb| (bb.min, bb.max));
+        (min.x..=max.x, min.y..=max.y)
+    }
+
+    /// Cells in row y, across the grid's area.
+    pub fn row(&self, y: i64) -> impl Iterator<Item = Option<&T>> {
+        self.spans().0.map(move |x| self.get(&Pos::new(x, y)))
+    }
+
+    /// Cells in column x, across the grid's area.
+    pub fn column(&self, x: i64) -> impl Iterator<Item = Option<&T>> {
+        self.spans().1.map(move |y| self.get(&Pos::new(x, y)))
+    }
+
+    /// All rows across the grid's area, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+        let (xs, ys) = self.spans();
+        ys.map(move |y| xs.clone().map(|x| self.get(&Pos::new(x, y))).collect())
+    }
+
+    /// All columns across the grid's area, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+        let (xs, ys) = self.spans();
+        xs.map(move |x| ys.clone().map(|y| self.get(&Pos::new(x, y))).collect())
+    }
+
+    /// Renders the grid's area as text, one line per row. `f` picks the character for each cell.
+    pub fn render<F: Fn(Option<&T>) -> char>(&self, f: F) -> String {
+        let mut out = String::new();
+        for row in self.rows() {
+            out.extend(row.into_iter().map(&f));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = ".#..#\n.....\n#####\n....#\n...##\n";
+
+    fn asteroid(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            _ => None,
+        }
+    }
+
+    fn show(cell: Option<&bool>) -> char {
+        if cell.is_some() {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let dense = Grid::parse_dense(MAP, asteroid);
+        let sparse = Grid::parse_sparse(MAP, asteroid);
+        assert!(!dense.is_sparse());
+        assert!(sparse.is_sparse());
+        assert_eq!(dense.len(), 10);
+        assert_eq!(sparse.len(), 10);
+        assert_eq!(dense.render(show), MAP);
+        assert_eq!(sparse.render(show), MAP);
+
+        // Sparse grids only cover the set cells.
+        let trimmed = Grid::parse_sparse("...\n.#.\n..#\n", asteroid);
+        assert_eq!(trimmed.render(show), "#.\n.#\n");
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = Grid::dense(3, 2);
+        assert_eq!(grid.get(&Pos::new(1, 1)), None);
+        assert_eq!(grid.set(Pos::new(1, 1), 'a'), None);
+        assert_eq!(grid.set(Pos::new(1, 1), 'b'), Some('a'));
+        *grid.get_mut(&Pos::new(1, 1)).unwrap() = 'c';
+        assert_eq!(grid.get(&Pos::new(1, 1)), Some(&'c'));
+        assert_eq!(grid.get(&Pos::new(-1, 1)), None);
+        assert_eq!(grid.get(&Pos::new(3, 0)), None);
+        assert_eq!(grid.remove(&Pos::new(1, 1)), Some('c'));
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_outside_dense() {
+        Grid::dense(3, 2).set(Pos::new(3, 0), 1);
+    }
+
+    #[test]
+    fn test_bounds_and_iteration() {
+        let mut grid = Grid::sparse();
+        grid.set(Pos::new(-2, 1), 1);
+        grid.set(Pos::new(3, -1), 2);
+        let bb = grid.bounds().unwrap();
+        assert_eq!((bb.min, bb.max), (Pos::new(-2, -1), Pos::new(3, 1)));
+        assert_eq!(grid.area(), Some(bb));
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.row(1).count(), 6);
+        assert_eq!(grid.row(1).next(), Some(Some(&1)));
+        assert_eq!(
+            grid.column(3).collect::<Vec<_>>(),
+            vec![Some(&2), None, None]
+        );
+        let columns: Vec<Vec<Option<&i32>>> = grid.columns().collect();
+        assert_eq!(columns.len(), 6);
+        assert_eq!(columns[5], grid.column(3).collect::<Vec<_>>());
+
+        let dense = Grid::parse_dense(MAP, asteroid);
+        assert_eq!(dense.bounds().unwrap().min, Pos::new(0, 0));
+        assert_eq!(dense.area().unwrap().max, Pos::new(4, 4));
+        let first: Vec<Pos> = dense.iter().take(3).map(|(p, _)| p).collect();
+        assert_eq!(first, vec![Pos::new(1, 0), Pos::new(4, 0), Pos::new(0, 2)]);
+        assert_eq!(Grid::<u8>::sparse().area(), None);
+        assert_eq!(Grid::<u8>::dense(0, 0).rows().count(), 0);
+    }
+}
@@ -0,0 +1,99 @@
+//! Loads puzzle input from a file, or from stdin when the path is "-".
+
+use crate::error::{Error, Result};
+use std::fs;
+use std::io::{self, Read};
+use std::process::exit;
+
+/// Path that means "read standard input".
+pub const STDIN: &str = "-";
+
+/// Input text with the name of where it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    /// File path, or "<stdin>".
+    pub name: String,
+    pub text: String,
+}
+
+impl Input {
+    /// Parses the text, naming this input in any error.
+    pub fn parse<T, F: FnOnce(&str) -> Result<T>>(&self, parse: F) -> Result<T> {
+        parse(&self.text).map_err(|e| e.in_file(&self.name))
+    }
+}
+
+/// Reads a file, or stdin if `path` is "-".
+pub fn read(path: &str) -> Result<Input> {
+    if path == STDIN {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|source| Error::Io {
+                path: "<stdin>".to_string(),
+                source,
+            })?;
+        return Ok(Input {
+            name: "<stdin>".to_string(),
+            text,
+        });
+    }
+    let text = fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    Ok(Input {
+        name: path.to_string(),
+        text,
+    })
+}
+
+/// Reads and parses an input in one go.
+pub fn load<T, F: FnOnce(&str) -> Result<T>>(path: &str, parse: F) -> Result<T> {
+    read(path)?.parse(parse)
+}
+
+/// Unwraps a result, or prints the error and exits. Meant for the binaries.
+pub fn or_exit<T>(result: Result<T>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1);
+    })
+}
+
+/// Non-blank lines with their line numbers, counting from 1, and surrounding whitespace trimmed.
+pub fn lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let numbered: Vec<(usize, &str)> = lines("a\n\n  b \r\n\nc").collect();
+        assert_eq!(numbered, vec![(1, "a"), (3, "b"), (5, "c")]);
+    }
+
+    #[test]
+    fn test_load() {
+        let parse = |text: &str| -> Result<usize> {
+            match lines(text).find(|(_, l)| l.parse::<u32>().is_err()) {
+                Some((n, l)) => Err(Error::at_line(n, format!("not a number: {}", l))),
+                None => Ok(lines(text).count()),
+            }
+        };
+        assert_eq!(load("inputs/day4.txt", |t| Ok(t.trim().len())).unwrap(), 13);
+        assert_eq!(
+            load("inputs/day6.txt", parse).unwrap_err().to_string(),
+            "inputs/day6.txt: line 1: not a number: MZP)PKY"
+        );
+        let e = load("inputs/missing.txt", parse).unwrap_err();
+        assert!(matches!(e, Error::Io { .. }));
+        assert!(e.to_string().starts_with("inputs/missing.txt: "));
+    }
+}
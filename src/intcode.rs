@@ -1,7 +1,7 @@
-use std::collections::{HashMap, VecDeque};
+//! Intcode interpreter.
 
-/// Intcode interpreter.
-use std::fs;
+use crate::input;
+use std::collections::{HashMap, VecDeque};
 
 #[derive(Debug, Default, Clone)]
 pub struct State {
@@ -20,34 +20,181 @@ pub enum StopReason {
     WaitingOnInput,
 }
 
-/// Parses comma-delimited string into a vector of ints.
-fn parse_ints(s: &str) -> Vec<i64> {
-    s.trim()
-        .split(',')
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect()
+/// Why a program text could not be loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgramErrorKind {
+    /// There was nothing between two commas.
+    EmptyToken,
+    /// Token is not a valid integer.
+    InvalidValue(String),
+    /// The address part of an "@addr=value" token is not a valid non-negative integer.
+    InvalidAddress(String),
+    /// Two tokens wrote to the same address.
+    DuplicateAddress(i64),
+    /// Value follows one at the largest address, so it has nowhere to go.
+    AddressOutOfRange,
+    /// Program text contains no values at all.
+    Empty,
+}
+
+/// Error returned when loading a program text. Points at the offending token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramError {
+    /// Index of the token, counting from 0. Tokens are separated by commas.
+    pub token: usize,
+    /// Byte offset of the token in the input.
+    pub offset: usize,
+    /// Line of the token, counting from 1.
+    pub line: usize,
+    /// Byte column of the token, counting from 1.
+    pub column: usize,
+    pub kind: ProgramErrorKind,
+}
+
+impl ProgramError {
+    fn new(input: &str, token: usize, offset: usize, kind: ProgramErrorKind) -> Self {
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = offset - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        ProgramError {
+            token,
+            offset,
+            line,
+            column,
+            kind,
+        }
+    }
+}
+
+impl std::fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "token {} (line {}, column {}, byte {}): ",
+            self.token, self.line, self.column, self.offset
+        )?;
+        match &self.kind {
+            ProgramErrorKind::EmptyToken => write!(f, "empty value"),
+            ProgramErrorKind::InvalidValue(v) => write!(f, "invalid value {:?}", v),
+            ProgramErrorKind::InvalidAddress(a) => write!(f, "invalid address {:?}", a),
+            ProgramErrorKind::DuplicateAddress(a) => write!(f, "address {} set twice", a),
+            ProgramErrorKind::AddressOutOfRange => write!(f, "address out of range"),
+            ProgramErrorKind::Empty => write!(f, "program is empty"),
+        }
+    }
+}
+
+impl std::error::Error for ProgramError {}
+
+/// Splits program text into comma-separated tokens.
+///
+/// Returns (byte offset, token) pairs with surrounding whitespace trimmed. A byte order mark and
+/// "#" comments (running to the end of the line) are blanked out first, so offsets still point
+/// into the original input. An empty token after the final comma is dropped.
+fn tokenize(s: &str) -> Vec<(usize, String)> {
+    let mut blanked = String::with_capacity(s.len());
+    let mut in_comment = false;
+    for (i, c) in s.char_indices() {
+        if c == '\n' {
+            in_comment = false;
+        } else if c == '#' {
+            in_comment = true;
+        }
+        if in_comment || (i == 0 && c == '\u{feff}') {
+            blanked.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        } else {
+            blanked.push(c);
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    for piece in blanked.split(',') {
+        let trimmed = piece.trim_start();
+        let offset = start + piece.len() - trimmed.len();
+        let len = trimmed.trim_end().len();
+        tokens.push((offset, trimmed[..len].to_string()));
+        start += piece.len() + 1;
+    }
+    if tokens.last().is_some_and(|(_, token)| token.is_empty()) {
+        tokens.pop();
+    }
+    tokens
 }
 
-fn parse_ints_to_hashmap(s: &str) -> HashMap<i64, i64> {
-    s.trim()
-        .split(',')
-        .enumerate()
-        .map(|(i, x)| (i as i64, x.parse::<i64>().unwrap()))
-        .collect()
+/// Loads program text into memory.
+///
+/// Values are comma-separated and may be surrounded by any whitespace, including newlines.
+/// Anything from "#" to the end of the line is a comment. A token of the form "@addr=value" puts
+/// value at addr, and the values that follow continue from addr + 1. This is the format that
+/// `memory_to_string` produces for memory with holes.
+pub fn parse_program(s: &str) -> Result<HashMap<i64, i64>, ProgramError> {
+    let mut memory = HashMap::new();
+    // None once a value has been put at the largest address.
+    let mut next: Option<i64> = Some(0);
+    let tokens = tokenize(s);
+    if tokens.is_empty() {
+        return Err(ProgramError::new(s, 0, s.len(), ProgramErrorKind::Empty));
+    }
+    for (i, (offset, token)) in tokens.into_iter().enumerate() {
+        let err = |kind| ProgramError::new(s, i, offset, kind);
+        if token.is_empty() {
+            return Err(err(ProgramErrorKind::EmptyToken));
+        }
+        let (addr, value_str) = if let Some(rest) = token.strip_prefix('@') {
+            let (addr_str, value_str) = rest
+                .split_once('=')
+                .ok_or_else(|| err(ProgramErrorKind::InvalidAddress(rest.to_string())))?;
+            let addr = match addr_str.trim().parse::<i64>() {
+                Ok(a) if a >= 0 => a,
+                _ => return Err(err(ProgramErrorKind::InvalidAddress(addr_str.to_string()))),
+            };
+            (addr, value_str.trim())
+        } else {
+            let addr = next.ok_or_else(|| err(ProgramErrorKind::AddressOutOfRange))?;
+            (addr, token.as_str())
+        };
+        let value = value_str
+            .parse::<i64>()
+            .map_err(|_| err(ProgramErrorKind::InvalidValue(value_str.to_string())))?;
+        if memory.insert(addr, value).is_some() {
+            return Err(err(ProgramErrorKind::DuplicateAddress(addr)));
+        }
+        next = addr.checked_add(1);
+    }
+    Ok(memory)
+}
+
+/// Parses comma-delimited string into a vector of ints, in address order.
+fn parse_ints(s: &str) -> Vec<i64> {
+    let memory = parse_program(s).unwrap();
+    let mut addrs: Vec<&i64> = memory.keys().collect();
+    addrs.sort();
+    addrs.iter().map(|addr| memory[addr]).collect()
 }
 
 impl State {
-    /// Creates State by reading a file and parsing it as comma-delimited string of integers.
+    /// Creates State by reading a program file, or stdin if `path` is "-".
+    pub fn load(path: &str) -> crate::Result<State> {
+        input::load(path, |text| Ok(State::parse(text)?))
+    }
+
+    /// Like `load`, but panics on error.
     pub fn from_file(path: &str) -> State {
-        State::from_string(&fs::read_to_string(path).unwrap())
+        State::load(path).unwrap_or_else(|e| panic!("{}", e))
     }
 
     /// Creates State by parsing a comma delimited string of integers.
     pub fn from_string(s: &str) -> State {
-        State {
-            memory: parse_ints_to_hashmap(s),
+        State::parse(s).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates State from program text, see `parse_program` for the accepted format.
+    pub fn parse(s: &str) -> Result<State, ProgramError> {
+        Ok(State {
+            memory: parse_program(s)?,
             ..Default::default()
-        }
+        })
     }
 
     /// Returns memory as comma-delimited string.
@@ -75,12 +222,12 @@ impl State {
             let value = self.memory[addr];
             added += &value.to_string();
             prev_addr = *addr;
-            String::from(s) + &added
+            s + &added
         })
     }
 
     /// Reads value at given address.
-    fn read(&self, addr: i64) -> i64 {
+    pub fn read(&self, addr: i64) -> i64 {
         assert!(addr >= 0);
         *self.memory.get(&addr).unwrap_or(&0)
     }
@@ -143,10 +290,10 @@ impl State {
         let mode3 = (opcode / 10000) % 10;
         match instruction {
             // Add or multiply:
-            01 | 02 => {
+            1 | 2 => {
                 let lhs = self.read_with_mode(mode1, self.arg1());
                 let rhs = self.read_with_mode(mode2, self.arg2());
-                let result = if instruction == 01 {
+                let result = if instruction == 1 {
                     lhs + rhs
                 } else {
                     lhs * rhs
@@ -155,7 +302,7 @@ impl State {
                 self.ip += 4;
             }
             // Input:
-            03 => {
+            3 => {
                 if self.inputs.is_empty() {
                     return Some(StopReason::WaitingOnInput);
                 }
@@ -165,34 +312,33 @@ impl State {
                 self.ip += 2;
             }
             // Output:
-            04 => {
+            4 => {
                 let value = self.read_with_mode(mode1, self.arg1());
                 // println!("OUTPUT: {}", value);
                 self.outputs.push_back(value);
                 self.ip += 2;
             }
-            // 05: Jump-if-true:
-            // 06: Jump-if-false:
-            05 | 06 => {
+            // 5: Jump-if-true:
+            // 6: Jump-if-false:
+            5 | 6 => {
                 let p1 = self.read_with_mode(mode1, self.arg1());
                 let p2 = self.read_with_mode(mode2, self.arg2());
-                if ((instruction == 05) && (p1 != 0)) || ((instruction == 06) && (p1 == 0)) {
+                if ((instruction == 5) && (p1 != 0)) || ((instruction == 6) && (p1 == 0)) {
                     self.ip = p2;
                 } else {
                     self.ip += 3;
                 }
             }
-            // 07: less than:
-            // 08: equals:
-            07 | 08 => {
+            // 7: less than:
+            // 8: equals:
+            7 | 8 => {
                 let p1 = self.read_with_mode(mode1, self.arg1());
                 let p2 = self.read_with_mode(mode2, self.arg2());
-                let value =
-                    ((instruction == 07) && (p1 < p2)) || ((instruction == 08) && (p1 == p2));
+                let value = ((instruction == 7) && (p1 < p2)) || ((instruction == 8) && (p1 == p2));
                 self.write_with_mode(mode3, self.arg3(), if value { 1 } else { 0 });
                 self.ip += 4;
             }
-            09 => {
+            9 => {
                 self.relative_base += self.read_with_mode(mode1, self.arg1());
                 self.ip += 2;
             }
@@ -234,24 +380,6 @@ impl State {
     }
 }
 
-/// "Find the input noun and verb that cause the program to produce the output 19690720. What is
-/// 100 * noun + verb?"
-pub fn day2_part2() -> i64 {
-    let mut state = State::from_file("inputs/day2_input.txt");
-    for noun in 0..=99 {
-        state.write(1, noun);
-        for verb in 0..=99 {
-            state.write(2, verb);
-            let mut state2 = state.clone();
-            state2.run(false);
-            if state2.read(0) == 19690720 {
-                return 100 * noun + verb;
-            }
-        }
-    }
-    panic!("could not find answer");
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +390,49 @@ mod tests {
         assert_eq!(state.memory_to_string(), "1,2,3");
     }
 
+    #[test]
+    fn test_parse_program_whitespace_and_comments() {
+        let text = "\u{feff}# header comment\n1, 2,\n  3 # trailing\n,4,\n";
+        assert_eq!(State::parse(text).unwrap().memory_to_string(), "1,2,3,4");
+    }
+
+    #[test]
+    fn test_parse_program_sparse() {
+        let state = State::from_string("1,2,3");
+        let mut sparse = state.clone();
+        sparse.write(10, 456);
+        sparse.write(11, -7);
+        let text = sparse.memory_to_string();
+        assert_eq!(text, "1,2,3,@10=456,-7");
+        assert_eq!(State::parse(&text).unwrap().memory_to_string(), text);
+    }
+
+    #[test]
+    fn test_parse_program_errors() {
+        let err = State::parse("1,2,\n3,x4,5").unwrap_err();
+        assert_eq!(err.kind, ProgramErrorKind::InvalidValue("x4".to_string()));
+        assert_eq!((err.token, err.offset, err.line, err.column), (3, 7, 2, 3));
+
+        let err = State::parse("1,,2").unwrap_err();
+        assert_eq!(err.kind, ProgramErrorKind::EmptyToken);
+        assert_eq!((err.token, err.offset), (1, 2));
+
+        let err = State::parse("1,2,@1=5").unwrap_err();
+        assert_eq!(err.kind, ProgramErrorKind::DuplicateAddress(1));
+
+        let err = State::parse("1,@-3=5").unwrap_err();
+        assert_eq!(err.kind, ProgramErrorKind::InvalidAddress("-3".to_string()));
+
+        let last = "@9223372036854775807=1";
+        assert_eq!(parse_program(last).unwrap()[&i64::MAX], 1);
+        let err = State::parse(&format!("{},2", last)).unwrap_err();
+        assert_eq!(err.kind, ProgramErrorKind::AddressOutOfRange);
+        assert_eq!((err.token, err.offset), (1, 23));
+
+        let err = State::parse(" # nothing here\n").unwrap_err();
+        assert_eq!(err.kind, ProgramErrorKind::Empty);
+    }
+
     #[test]
     fn test_day2_example() {
         let mut state = State::from_string("1,9,10,3,2,3,11,0,99,30,40,50");
@@ -272,11 +443,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_day2_part2() {
-        assert_eq!(day2_part2(), 9425);
-    }
-
     #[test]
     fn test_day5_example() {
         let mut state = State::from_string("1002,4,3,4,33");
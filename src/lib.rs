@@ -1,4 +1,20 @@
+pub mod asteroids;
+pub mod bench;
+pub mod combinatorics;
+pub mod days;
+pub mod error;
+pub mod geom;
+pub mod grid;
+pub mod input;
 #[allow(dead_code)]
 pub mod intcode;
+pub mod ocr;
+pub mod password;
+pub mod regression;
+pub mod sif;
+pub mod solution;
+pub mod tree;
 #[allow(dead_code)]
 pub mod wires;
+
+pub use error::{Error, Result};
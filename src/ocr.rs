@@ -0,0 +1,248 @@
+//! Reads text drawn in the block capital font that puzzles use for answers, like the day 8
+//! image and the day 11 hull painting.
+//!
+//! Letters are 6 pixels tall and 4 wide, except Y which is 5, with a blank column between them.
+//! Blank rows and columns around the text are ignored, and so is extra space between letters.
+
+use crate::error::{Error, Result};
+
+/// Known letters, '#' for set pixels. Columns and rows that are blank in every letter are
+/// trimmed before comparing, so I can be narrower than the rest.
+const FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// A glyph that doesn't match any known letter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unknown {
+    /// Position of its '?' in the text, in characters.
+    pub index: usize,
+    /// First column of the glyph in the input.
+    pub column: usize,
+    /// The glyph's pixels, '#' and '.', one line per row.
+    pub art: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// Recognised letters, with '?' for unknown glyphs.
+    pub text: String,
+    pub unknown: Vec<Unknown>,
+}
+
+impl Reading {
+    /// The text, or an error describing the first unknown glyph.
+    pub fn into_result(self) -> Result<String> {
+        match self.unknown.first() {
+            None => Ok(self.text),
+            Some(u) => Err(Error::parse(format!(
+                "unknown glyph at column {} (read {:?} so far):\n{}",
+                u.column,
+                &self.text[..u.index],
+                u.art
+            ))),
+        }
+    }
+}
+
+/// Pixels with blank outer rows and columns removed, as '#'/'.' lines.
+fn trimmed(rows: &[Vec<bool>]) -> String {
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let set = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let used_cols: Vec<usize> = (0..width)
+        .filter(|x| (0..rows.len()).any(|y| set(*x, y)))
+        .collect();
+    let used_rows: Vec<usize> = (0..rows.len())
+        .filter(|y| (0..width).any(|x| set(x, *y)))
+        .collect();
+    let (cols, rows_used) = match (
+        used_cols.first(),
+        used_cols.last(),
+        used_rows.first(),
+        used_rows.last(),
+    ) {
+        (Some(x0), Some(x1), Some(y0), Some(y1)) => (*x0..=*x1, *y0..=*y1),
+        _ => return String::new(),
+    };
+    rows_used
+        .map(|y| {
+            cols.clone()
+                .map(|x| if set(x, y) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn font() -> Vec<(char, String)> {
+    FONT.iter()
+        .map(|(c, art)| {
+            let rows: Vec<Vec<bool>> = art
+                .iter()
+                .map(|r| r.chars().map(|c| c == '#').collect())
+                .collect();
+            (*c, trimmed(&rows))
+        })
+        .collect()
+}
+
+/// Columns `from..to` of the rows, trimmed.
+fn glyph(rows: &[Vec<bool>], from: usize, to: usize) -> String {
+    let cols: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|r| {
+            (from..to)
+                .map(|x| r.get(x).copied().unwrap_or(false))
+                .collect()
+        })
+        .collect();
+    trimmed(&cols)
+}
+
+/// Reads letters from rows of pixels, true for set. Rows may have different lengths; missing
+/// pixels are unset. Letters are split at columns with no set pixels, or where a known letter
+/// ends, since Y fills its whole 5 column cell and can touch the next letter.
+pub fn read(rows: &[Vec<bool>]) -> Reading {
+    let font = font();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let blank = |x: usize| rows.iter().all(|r| !r.get(x).copied().unwrap_or(false));
+
+    let mut reading = Reading {
+        text: String::new(),
+        unknown: Vec::new(),
+    };
+    let mut x = 0;
+    while x < width {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+        let mut end = x;
+        while end < width && !blank(end) {
+            end += 1;
+        }
+        let art = glyph(rows, x, end);
+        if let Some((c, _)) = font.iter().find(|(_, known)| *known == art) {
+            reading.text.push(*c);
+            x = end;
+            continue;
+        }
+        // Maybe several letters touching: the first must start right here.
+        let prefix = font.iter().find(|(_, known)| {
+            let w = known.lines().next().map_or(0, |l| l.len());
+            w < end - x && glyph(rows, x, x + w) == *known
+        });
+        match prefix {
+            Some((c, known)) => {
+                reading.text.push(*c);
+                x += known.lines().next().map_or(0, |l| l.len());
+            }
+            None => {
+                reading.unknown.push(Unknown {
+                    index: reading.text.len(),
+                    column: x,
+                    art,
+                });
+                reading.text.push('?');
+                x = end;
+            }
+        }
+    }
+    reading
+}
+
+/// Like `read`, for text art where `on` marks set pixels.
+pub fn read_art(art: &str, on: char) -> Reading {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(|c| c == on).collect())
+        .collect();
+    read(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws text in the font, each letter in a 5 column cell like the puzzles do. Y fills its
+    /// cell, so it touches the next letter.
+    fn draw(text: &str) -> String {
+        let mut rows = vec![String::new(); 6];
+        for c in text.chars() {
+            let (_, art) = FONT.iter().find(|(l, _)| *l == c).unwrap();
+            for (row, line) in rows.iter_mut().zip(art.iter()) {
+                row.push_str(&format!("{:.<5}", line));
+            }
+        }
+        rows.join("\n")
+    }
+
+    #[test]
+    fn test_font() {
+        let all: String = FONT.iter().map(|(c, _)| *c).collect();
+        let reading = read_art(&draw(&all), '#');
+        assert_eq!(reading.text, all);
+        assert!(reading.unknown.is_empty());
+    }
+
+    #[test]
+    fn test_touching() {
+        assert_eq!(read_art(&draw("YAY"), '#').text, "YAY");
+        assert_eq!(read_art(&draw("GYZ"), '#').into_result().unwrap(), "GYZ");
+    }
+
+    #[test]
+    fn test_padding() {
+        // Blank rows and columns around the text.
+        let art = format!("\n\n{}\n......", draw("HI"))
+            .lines()
+            .map(|l| format!("...{}..", l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(read_art(&art, '#').into_result().unwrap(), "HI");
+        assert_eq!(read_art("", '#').text, "");
+        // Different row lengths, as when trailing spaces are trimmed.
+        assert_eq!(
+            read_art(&draw("LU").replace('.', " ").replace(" \n", "\n"), '#').text,
+            "LU"
+        );
+    }
+
+    #[test]
+    fn test_unknown() {
+        let mut lines: Vec<String> = draw("AB").lines().map(|l| l.to_string()).collect();
+        // Break the B.
+        lines[2].replace_range(5..9, "####");
+        let reading = read_art(&lines.join("\n"), '#');
+        assert_eq!(reading.text, "A?");
+        assert_eq!(
+            reading.unknown,
+            vec![Unknown {
+                index: 1,
+                column: 5,
+                art: "###.\n#..#\n####\n#..#\n#..#\n###.".to_string()
+            }]
+        );
+        assert_eq!(
+            reading.into_result().unwrap_err().to_string(),
+            "unknown glyph at column 5 (read \"A\" so far):\n###.\n#..#\n####\n#..#\n#..#\n###."
+        );
+    }
+}
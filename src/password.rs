@@ -0,0 +1,381 @@
+//! Password rules, as in day 4: a password is a number whose decimal digits satisfy every rule
+//! in a set.
+//!
+//! Rules can be written as text, e.g. on the command line:
+//!
+//! * `digits=6`: exactly six digits, no leading zeros.
+//! * `monotonic=up`: digits never decrease left to right. Also `down`, `strict-up` and
+//!   `strict-down`.
+//! * `run=2`: some run of equal digits is exactly two long. `run>=2` means at least two.
+//! * `forbid=09`: digits 0 and 9 don't appear.
+//! * `range=100-200`: the number is between 100 and 200, inclusive.
+
+use crate::error::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+pub mod count;
+
+/// Direction the digits must go in, left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    NonDecreasing,
+    NonIncreasing,
+    Increasing,
+    Decreasing,
+}
+
+impl Order {
+    /// True if digit `b` may follow digit `a`.
+    pub fn allows(self, a: u8, b: u8) -> bool {
+        match self {
+            Order::NonDecreasing => a <= b,
+            Order::NonIncreasing => a >= b,
+            Order::Increasing => a < b,
+            Order::Decreasing => a > b,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Order::NonDecreasing => "up",
+            Order::NonIncreasing => "down",
+            Order::Increasing => "strict-up",
+            Order::Decreasing => "strict-down",
+        }
+    }
+}
+
+/// Required length of some run of equal adjacent digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunLength {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl RunLength {
+    pub fn allows(self, len: usize) -> bool {
+        match self {
+            RunLength::Exactly(n) => len == n,
+            RunLength::AtLeast(n) => len >= n,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Number of digits, without leading zeros.
+    DigitCount(usize),
+    Monotonic(Order),
+    /// Some run of equal adjacent digits has this length.
+    Run(RunLength),
+    /// Digits that must not appear.
+    Forbidden(Vec<u8>),
+    /// Inclusive range the number must be in.
+    Range(u64, u64),
+}
+
+/// Decimal digits of a number, most significant first.
+pub fn digits(mut n: u64) -> Vec<u8> {
+    let mut digits = vec![(n % 10) as u8];
+    n /= 10;
+    while n > 0 {
+        digits.push((n % 10) as u8);
+        n /= 10;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Lengths of the runs of equal adjacent digits, left to right.
+fn runs(digits: &[u8]) -> Vec<usize> {
+    let mut runs = Vec::new();
+    let mut len = 1;
+    for i in 1..=digits.len() {
+        if i < digits.len() && digits[i] == digits[i - 1] {
+            len += 1;
+        } else {
+            runs.push(len);
+            len = 1;
+        }
+    }
+    runs
+}
+
+impl Rule {
+    /// Checks a number, given along with its digits.
+    pub fn check(&self, n: u64, digits: &[u8]) -> bool {
+        match self {
+            Rule::DigitCount(count) => digits.len() == *count,
+            Rule::Monotonic(order) => digits.windows(2).all(|w| order.allows(w[0], w[1])),
+            Rule::Run(len) => runs(digits).into_iter().any(|run| len.allows(run)),
+            Rule::Forbidden(forbidden) => !digits.iter().any(|d| forbidden.contains(d)),
+            Rule::Range(from, to) => *from <= n && n <= *to,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::DigitCount(count) => write!(f, "digits={}", count),
+            Rule::Monotonic(order) => write!(f, "monotonic={}", order.name()),
+            Rule::Run(RunLength::Exactly(n)) => write!(f, "run={}", n),
+            Rule::Run(RunLength::AtLeast(n)) => write!(f, "run>={}", n),
+            Rule::Forbidden(forbidden) => {
+                write!(f, "forbid=")?;
+                forbidden.iter().try_for_each(|d| write!(f, "{}", d))
+            }
+            Rule::Range(from, to) => write!(f, "range={}-{}", from, to),
+        }
+    }
+}
+
+/// Parses a range like "109165-576723".
+pub fn parse_range(s: &str) -> Result<(u64, u64)> {
+    let err = || Error::parse(format!("expected range like 100-200, got {:?}", s.trim()));
+    let (from, to) = s.trim().split_once('-').ok_or_else(err)?;
+    let from: u64 = from.trim().parse().map_err(|_| err())?;
+    let to: u64 = to.trim().parse().map_err(|_| err())?;
+    if from > to {
+        return Err(Error::parse(format!("range {}-{} is backwards", from, to)));
+    }
+    Ok((from, to))
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Rule> {
+        let s = s.trim();
+        let err = |what: &str| Error::parse(format!("invalid {} in rule {:?}", what, s));
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| Error::parse(format!("expected name=value, got {:?}", s)))?;
+        let count = |v: &str| v.trim().parse::<usize>().map_err(|_| err("count"));
+        match name.split_whitespace().collect::<String>().as_str() {
+            "digits" => Ok(Rule::DigitCount(count(value)?)),
+            "monotonic" => {
+                let order = [
+                    Order::NonDecreasing,
+                    Order::NonIncreasing,
+                    Order::Increasing,
+                    Order::Decreasing,
+                ]
+                .iter()
+                .copied()
+                .find(|o| o.name() == value.trim())
+                .ok_or_else(|| err("direction"))?;
+                Ok(Rule::Monotonic(order))
+            }
+            "run" => Ok(Rule::Run(RunLength::Exactly(count(value)?))),
+            "run>" => Ok(Rule::Run(RunLength::AtLeast(count(value)?))),
+            "forbid" => value
+                .trim()
+                .chars()
+                .map(|c| c.to_digit(10).map(|d| d as u8).ok_or_else(|| err("digit")))
+                .collect::<Result<_>>()
+                .map(Rule::Forbidden),
+            "range" => {
+                let (from, to) = parse_range(value)?;
+                Ok(Rule::Range(from, to))
+            }
+            other => Err(Error::parse(format!("unknown rule {:?}", other))),
+        }
+    }
+}
+
+/// Rules that must all hold.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn new() -> RuleSet {
+        RuleSet::default()
+    }
+
+    /// Day 4 part 1: six digits in range, never decreasing, with two or more equal adjacent
+    /// digits.
+    pub fn part1(from: u64, to: u64) -> RuleSet {
+        RuleSet::new()
+            .with(Rule::DigitCount(6))
+            .with(Rule::Range(from, to))
+            .with(Rule::Monotonic(Order::NonDecreasing))
+            .with(Rule::Run(RunLength::AtLeast(2)))
+    }
+
+    /// Day 4 part 2: like part 1, but some pair of equal digits isn't part of a longer run.
+    pub fn part2(from: u64, to: u64) -> RuleSet {
+        RuleSet::new()
+            .with(Rule::DigitCount(6))
+            .with(Rule::Range(from, to))
+            .with(Rule::Monotonic(Order::NonDecreasing))
+            .with(Rule::Run(RunLength::Exactly(2)))
+    }
+
+    pub fn with(mut self, rule: Rule) -> RuleSet {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn push(&mut self, rule: Rule) {
+        self.rules.push(rule);
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn matches(&self, n: u64) -> bool {
+        let digits = digits(n);
+        self.rules.iter().all(|rule| rule.check(n, &digits))
+    }
+
+    /// Smallest range containing every number the rules allow, if the rules limit it.
+    pub fn bounds(&self) -> Option<(u64, u64)> {
+        let mut bounds: Option<(u64, u64)> = None;
+        for rule in self.rules.iter() {
+            let (from, to) = match *rule {
+                Rule::Range(from, to) => (from, to),
+                Rule::DigitCount(0) => (1, 0),
+                Rule::DigitCount(count) if count <= 20 => {
+                    let from = if count == 1 {
+                        0
+                    } else {
+                        10u64.pow(count as u32 - 1)
+                    };
+                    let to = 10u64.checked_pow(count as u32).map_or(u64::MAX, |n| n - 1);
+                    (from, to)
+                }
+                Rule::DigitCount(_) => (1, 0),
+                _ => continue,
+            };
+            bounds = Some(match bounds {
+                Some((f, t)) => (f.max(from), t.min(to)),
+                None => (from, to),
+            });
+        }
+        bounds
+    }
+
+    fn require_bounds(&self) -> Result<(u64, u64)> {
+        self.bounds()
+            .ok_or_else(|| Error::parse("rules don't limit the search; add a range or digit count"))
+    }
+
+    /// Counts matching numbers without visiting them, see `count::count`.
+    pub fn count(&self) -> Result<u64> {
+        let (from, to) = self.require_bounds()?;
+        count::count(self, from, to)
+    }
+
+    /// Counts matching numbers by checking each one in `bounds`. Only practical for small
+    /// ranges, but obviously right.
+    pub fn count_scan(&self) -> Result<u64> {
+        let (from, to) = self.require_bounds()?;
+        if from > to {
+            return Ok(0);
+        }
+        Ok((from..=to).filter(|n| self.matches(*n)).count() as u64)
+    }
+}
+
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rules: Vec<String> = self.rules.iter().map(Rule::to_string).collect();
+        write!(f, "{}", rules.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(0), vec![0]);
+        assert_eq!(digits(1203), vec![1, 2, 0, 3]);
+        assert_eq!(runs(&digits(112233)), vec![2, 2, 2]);
+        assert_eq!(runs(&digits(123444)), vec![1, 1, 1, 3]);
+        assert_eq!(runs(&digits(111122)), vec![4, 2]);
+    }
+
+    #[test]
+    fn test_presets() {
+        // Examples from the puzzle.
+        let part1 = RuleSet::part1(0, 999999);
+        assert!(part1.matches(111111));
+        assert!(!part1.matches(223450));
+        assert!(!part1.matches(123789));
+        let part2 = RuleSet::part2(0, 999999);
+        assert!(part2.matches(112233));
+        assert!(!part2.matches(123444));
+        assert!(part2.matches(111122));
+
+        assert_eq!(RuleSet::part1(109165, 576723).count().unwrap(), 2814);
+        assert_eq!(RuleSet::part2(109165, 576723).count().unwrap(), 1991);
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = RuleSet::new()
+            .with(Rule::DigitCount(3))
+            .with(Rule::Monotonic(Order::Decreasing))
+            .with(Rule::Forbidden(vec![0]));
+        assert!(rules.matches(321));
+        assert!(!rules.matches(320));
+        assert!(!rules.matches(331));
+        assert!(!rules.matches(4321));
+        assert_eq!(rules.bounds(), Some((100, 999)));
+        // 3 distinct digits from 1..=9 in decreasing order.
+        assert_eq!(rules.count().unwrap(), 84);
+
+        assert_eq!(
+            RuleSet::new().with(Rule::DigitCount(1)).bounds(),
+            Some((0, 9))
+        );
+        assert_eq!(
+            RuleSet::new().with(Rule::DigitCount(21)).count().unwrap(),
+            0
+        );
+        assert!(RuleSet::new()
+            .with(Rule::Forbidden(vec![1]))
+            .count()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        for text in [
+            "digits=6",
+            "monotonic=up",
+            "monotonic=strict-down",
+            "run=2",
+            "run>=3",
+            "forbid=09",
+            "range=5-10",
+        ]
+        .iter()
+        {
+            let rule: Rule = text.parse().unwrap();
+            assert_eq!(rule.to_string(), *text);
+        }
+        assert_eq!(
+            " run >= 2".parse::<Rule>().unwrap(),
+            Rule::Run(RunLength::AtLeast(2))
+        );
+
+        let err = |s: &str| s.parse::<Rule>().unwrap_err().to_string();
+        assert_eq!(err("digits"), "expected name=value, got \"digits\"");
+        assert_eq!(err("digits=x"), "invalid count in rule \"digits=x\"");
+        assert_eq!(
+            err("monotonic=sideways"),
+            "invalid direction in rule \"monotonic=sideways\""
+        );
+        assert_eq!(err("forbid=1a"), "invalid digit in rule \"forbid=1a\"");
+        assert_eq!(err("range=9-1"), "range 9-1 is backwards");
+        assert_eq!(err("colour=red"), "unknown rule \"colour\"");
+    }
+}
@@ -0,0 +1,293 @@
+//! Counts numbers matching a rule set without visiting each one.
+//!
+//! Works one digit count at a time, choosing digits left to right (digit DP). The state is the
+//! previous digit, the length of the current run of equal digits, which run rules are already
+//! satisfied, and whether the prefix still equals the lower or upper bound's prefix. Prefixes in
+//! the same state have the same number of valid completions, so each state is counted once. This
+//! handles any range of u64, which would take far too long to scan.
+
+use super::{Order, Rule, RuleSet, RunLength};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Rules in the form the DP needs. Range and digit count are handled by the bounds.
+struct Constraints {
+    orders: Vec<Order>,
+    /// Bit d is set if digit d may not appear.
+    forbidden: u16,
+    runs: Vec<RunLength>,
+}
+
+/// Most run rules a rule set can have, one bit each in `State::satisfied`.
+const MAX_RUNS: usize = 64;
+
+impl Constraints {
+    fn new(rules: &RuleSet) -> Result<Constraints> {
+        let mut constraints = Constraints {
+            orders: Vec::new(),
+            forbidden: 0,
+            runs: Vec::new(),
+        };
+        for rule in rules.rules() {
+            match rule {
+                Rule::Monotonic(order) => constraints.orders.push(*order),
+                Rule::Forbidden(digits) => {
+                    // Anything above 9 is never a digit, so forbidding it changes nothing.
+                    for d in digits.iter().filter(|d| **d <= 9) {
+                        constraints.forbidden |= 1 << d;
+                    }
+                }
+                Rule::Run(len) => constraints.runs.push(*len),
+                Rule::DigitCount(_) | Rule::Range(..) => {}
+            }
+        }
+        if constraints.runs.len() > MAX_RUNS {
+            return Err(Error::limit(format!(
+                "too many run rules: {}, at most {} can be counted",
+                constraints.runs.len(),
+                MAX_RUNS
+            )));
+        }
+        Ok(constraints)
+    }
+
+    /// Run rules satisfied by the run in progress, as a bit mask.
+    fn ongoing(&self, run: usize) -> u64 {
+        self.mask(|len| matches!(len, RunLength::AtLeast(n) if run >= n))
+    }
+
+    /// Run rules satisfied by a run that has just ended, as a bit mask.
+    fn ended(&self, run: usize) -> u64 {
+        self.mask(|len| len.allows(run))
+    }
+
+    fn mask<F: Fn(RunLength) -> bool>(&self, f: F) -> u64 {
+        self.runs
+            .iter()
+            .enumerate()
+            .filter(|(_, len)| f(**len))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    fn all_runs(&self) -> u64 {
+        self.mask(|_| true)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    pos: usize,
+    prev: u8,
+    run: usize,
+    satisfied: u64,
+    tight_low: bool,
+    tight_high: bool,
+}
+
+struct Counter<'a> {
+    constraints: &'a Constraints,
+    low: Vec<u8>,
+    high: Vec<u8>,
+    memo: HashMap<State, u64>,
+}
+
+impl<'a> Counter<'a> {
+    /// Numbers with the same digit count as `low` and `high`, between them.
+    fn count(&mut self, state: State) -> u64 {
+        let c = self.constraints;
+        if state.pos == self.low.len() {
+            let satisfied = state.satisfied | c.ended(state.run);
+            return if satisfied == c.all_runs() { 1 } else { 0 };
+        }
+        if let Some(n) = self.memo.get(&state) {
+            return *n;
+        }
+        let min = if state.tight_low {
+            self.low[state.pos]
+        } else {
+            0
+        };
+        let max = if state.tight_high {
+            self.high[state.pos]
+        } else {
+            9
+        };
+        let mut total = 0;
+        for d in min..=max {
+            if c.forbidden & 1 << d != 0 {
+                continue;
+            }
+            let mut next = State {
+                pos: state.pos + 1,
+                prev: d,
+                run: 1,
+                satisfied: state.satisfied,
+                tight_low: state.tight_low && d == min,
+                tight_high: state.tight_high && d == max,
+            };
+            if state.pos > 0 {
+                if !c.orders.iter().all(|o| o.allows(state.prev, d)) {
+                    continue;
+                }
+                if d == state.prev {
+                    next.run = state.run + 1;
+                } else {
+                    next.satisfied |= c.ended(state.run);
+                }
+            }
+            next.satisfied |= c.ongoing(next.run);
+            total += self.count(next);
+        }
+        self.memo.insert(state, total);
+        total
+    }
+}
+
+/// Counts numbers in `from..=to` that match every rule. Fails if there are more than
+/// `u64::MAX` of them, or too many run rules.
+pub fn count(rules: &RuleSet, from: u64, to: u64) -> Result<u64> {
+    let (from, to) = match rules.bounds() {
+        Some((low, high)) => (from.max(low), to.min(high)),
+        None => (from, to),
+    };
+    if from > to {
+        return Ok(0);
+    }
+    let constraints = Constraints::new(rules)?;
+    let mut total: u64 = 0;
+    for len in super::digits(from).len()..=super::digits(to).len() {
+        // Numbers with exactly `len` digits.
+        let smallest = if len == 1 {
+            0
+        } else {
+            10u64.pow(len as u32 - 1)
+        };
+        let largest = 10u64.checked_pow(len as u32).map_or(u64::MAX, |n| n - 1);
+        let mut counter = Counter {
+            constraints: &constraints,
+            low: super::digits(from.max(smallest)),
+            high: super::digits(to.min(largest)),
+            memo: HashMap::new(),
+        };
+        // Numbers of one digit count are at most u64::MAX - 10^19 + 1, so only the total can
+        // overflow.
+        let n = counter.count(State {
+            pos: 0,
+            prev: 0,
+            run: 0,
+            satisfied: 0,
+            tight_low: true,
+            tight_high: true,
+        });
+        total = total
+            .checked_add(n)
+            .ok_or_else(|| Error::limit("more than u64::MAX numbers match"))?;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    /// Rule sets to compare against scanning.
+    fn cases() -> Vec<RuleSet> {
+        let up = Rule::Monotonic(Order::NonDecreasing);
+        vec![
+            RuleSet::new(),
+            RuleSet::new().with(up.clone()),
+            RuleSet::new()
+                .with(up.clone())
+                .with(Rule::Run(RunLength::AtLeast(2))),
+            RuleSet::new()
+                .with(up)
+                .with(Rule::Run(RunLength::Exactly(2))),
+            RuleSet::new().with(Rule::Run(RunLength::Exactly(1))),
+            RuleSet::new().with(Rule::Run(RunLength::AtLeast(3))),
+            RuleSet::new()
+                .with(Rule::Run(RunLength::Exactly(2)))
+                .with(Rule::Run(RunLength::Exactly(3))),
+            RuleSet::new()
+                .with(Rule::Monotonic(Order::Decreasing))
+                .with(Rule::Forbidden(vec![0, 5])),
+            RuleSet::new()
+                .with(Rule::Monotonic(Order::NonIncreasing))
+                .with(Rule::Run(RunLength::AtLeast(2)))
+                .with(Rule::DigitCount(4)),
+            RuleSet::new()
+                .with(Rule::Forbidden(vec![1]))
+                .with(Rule::Range(250, 7000)),
+            RuleSet::new().with(Rule::Forbidden(vec![3, 16, 255])),
+        ]
+    }
+
+    #[test]
+    fn test_matches_scan() {
+        let ranges = [
+            (0, 0),
+            (0, 9),
+            (5, 120),
+            (0, 99999),
+            (987, 12345),
+            (1000, 1000),
+        ];
+        for rules in cases() {
+            for &(from, to) in ranges.iter() {
+                let rules = rules.clone().with(Rule::Range(from, to));
+                assert_eq!(
+                    rules.count().unwrap(),
+                    rules.count_scan().unwrap(),
+                    "{}",
+                    rules
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_presets() {
+        for (from, to) in [(109165, 576723), (100000, 999999), (234567, 234599)].iter() {
+            let part1 = RuleSet::part1(*from, *to);
+            assert_eq!(part1.count().unwrap(), part1.count_scan().unwrap());
+            let part2 = RuleSet::part2(*from, *to);
+            assert_eq!(part2.count().unwrap(), part2.count_scan().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        // Non-decreasing sequences of n digits from 1..=9: C(n + 8, 8).
+        let up = RuleSet::new()
+            .with(Rule::Monotonic(Order::NonDecreasing))
+            .with(Rule::DigitCount(18));
+        assert_eq!(up.count().unwrap(), 1_562_275);
+        // Every 18-digit number.
+        let all = RuleSet::new().with(Rule::DigitCount(18));
+        assert_eq!(all.count().unwrap(), 900_000_000_000_000_000);
+        // Every u64 but 0.
+        let all = RuleSet::new().with(Rule::Range(1, u64::MAX));
+        assert_eq!(all.count().unwrap(), u64::MAX);
+        // Every u64 is one too many to count.
+        let all = RuleSet::new().with(Rule::Range(0, u64::MAX));
+        assert_eq!(
+            all.count().unwrap_err().to_string(),
+            "more than u64::MAX numbers match"
+        );
+        let upper = RuleSet::new().with(Rule::Range(u64::MAX - 5, u64::MAX));
+        assert_eq!(upper.count().unwrap(), upper.count_scan().unwrap());
+    }
+
+    #[test]
+    fn test_too_many_runs() {
+        let runs = |n| {
+            (1..=n).fold(RuleSet::new().with(Rule::DigitCount(3)), |rules, _| {
+                rules.with(Rule::Run(RunLength::AtLeast(1)))
+            })
+        };
+        assert_eq!(runs(64).count().unwrap(), 900);
+        assert_eq!(
+            runs(65).count().unwrap_err().to_string(),
+            "too many run rules: 65, at most 64 can be counted"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Checks solutions against known answers, so shared code can be refactored safely.
+//!
+//! Expected answers live in a text file with one answer per line: day, part and the answer,
+//! separated by whitespace. Blank lines and lines starting with "#" are ignored. Answers spanning
+//! several lines (pictures) are written with "\n" escapes; trailing spaces are ignored when
+//! comparing.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::{Part, Runner};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_ANSWERS: &str = "inputs/answers.txt";
+
+/// Comment written at the top of a recorded answers file.
+pub const ANSWERS_HEADER: &str = "# Expected answers: <day> <part> <answer>
+# Pictures use \\n between rows; a leading \\ keeps leading spaces.
+# Check with `aoc check`; update with `aoc check --record`.
+";
+
+/// Known answers keyed by (day, part).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u32, Part), String>);
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |msg: &str| Error::at_line(i + 1, msg);
+            let (day, rest) = split_field(line);
+            let (part, answer) = split_field(rest);
+            let day: u32 = day.parse().map_err(|_| err("invalid day"))?;
+            let part = part
+                .parse()
+                .ok()
+                .and_then(Part::from_number)
+                .ok_or_else(|| err("part must be 1 or 2"))?;
+            let answer = normalize(&unescape(answer));
+            if answer.is_empty() {
+                return Err(err("missing answer"));
+            }
+            if answers.insert((day, part), answer).is_some() {
+                return Err(err("duplicate answer"));
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn from_file(path: &str) -> Result<Answers> {
+        input::load(path, Answers::parse)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u32, part: Part, answer: &str) {
+        self.0.insert((day, part), normalize(answer));
+    }
+}
+
+/// Writes answers in the format `Answers::parse` reads.
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((day, part), answer) in self.0.iter() {
+            writeln!(f, "{} {} {}", day, part, escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits off the first whitespace-separated field.
+fn split_field(s: &str) -> (&str, &str) {
+    match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], s[i..].trim_start()),
+        None => (s, ""),
+    }
+}
+
+/// Escapes backslashes and newlines, and a leading space which parsing would otherwise trim.
+fn escape(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\").replace('\n', "\\n");
+    if escaped.starts_with(' ') {
+        format!("\\{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Drops trailing whitespace on each line, and trailing empty lines.
+fn normalize(s: &str) -> String {
+    let lines: Vec<&str> = s.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_end().to_string()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The solution ran, but there is no known answer to compare with.
+    Unknown {
+        actual: String,
+    },
+    /// The input could not be read, or the solution panicked.
+    Fail(String),
+}
+
+/// Result of checking one part of one day.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub day: u32,
+    pub part: Part,
+    pub status: Status,
+    /// Time taken to parse the input and solve the part.
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub outcomes: Vec<Outcome>,
+}
+
+impl Report {
+    /// True if nothing failed or mismatched.
+    pub fn ok(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|o| matches!(o.status, Status::Pass | Status::Unknown { .. }))
+    }
+
+    fn count<F: Fn(&Status) -> bool>(&self, f: F) -> usize {
+        self.outcomes.iter().filter(|o| f(&o.status)).count()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for o in self.outcomes.iter() {
+            let (label, detail) = match &o.status {
+                Status::Pass => ("PASS", String::new()),
+                Status::Mismatch { expected, actual } => (
+                    "MISMATCH",
+                    format!(
+                        "expected {:?}, got {:?}",
+                        normalize(expected),
+                        normalize(actual)
+                    ),
+                ),
+                Status::Unknown { actual } => {
+                    ("UNKNOWN", format!("no expected answer, got {:?}", actual))
+                }
+                Status::Fail(msg) => ("FAIL", msg.clone()),
+            };
+            let line = format!(
+                "day {:>2} part {}  {:<8} {:>10.3?}  {}",
+                o.day, o.part, label, o.time, detail
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        let total: Duration = self.outcomes.iter().map(|o| o.time).sum();
+        write!(
+            f,
+            "{} passed, {} mismatched, {} failed, {} unknown in {:.3?}",
+            self.count(|s| *s == Status::Pass),
+            self.count(|s| matches!(s, Status::Mismatch { .. })),
+            self.count(|s| matches!(s, Status::Fail(_))),
+            self.count(|s| matches!(s, Status::Unknown { .. })),
+            total
+        )
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// Runs one part of a solution on given input text and compares with the expected answer.
+pub fn check_part(
+    solution: &dyn Runner,
+    input: &str,
+    part: Part,
+    expected: Option<&str>,
+) -> Outcome {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solution.run(input, &[part])));
+    let time = start.elapsed();
+    let status = match result {
+        Err(payload) => Status::Fail(panic_message(payload)),
+        Ok(Err(e)) => Status::Fail(e.to_string()),
+        Ok(Ok(mut answers)) => {
+            let actual = answers.remove(0);
+            match expected {
+                None => Status::Unknown { actual },
+                Some(expected) if normalize(expected) == normalize(&actual) => Status::Pass,
+                Some(expected) => Status::Mismatch {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            }
+        }
+    };
+    Outcome {
+        day: solution.day(),
+        part,
+        status,
+        time,
+    }
+}
+
+/// Runs both parts of every given solution against its default input.
+pub fn run(solutions: &[Box<dyn Runner>], answers: &Answers) -> Report {
+    let mut outcomes = Vec::new();
+    for solution in solutions.iter() {
+        match input::read(&solution.default_input()) {
+            Ok(input) => {
+                for part in Part::BOTH.iter() {
+                    let expected = answers.get(solution.day(), *part);
+                    let mut outcome = check_part(solution.as_ref(), &input.text, *part, expected);
+                    if let Status::Fail(msg) = &mut outcome.status {
+                        *msg = format!("{}: {}", input.name, msg);
+                    }
+                    outcomes.push(outcome);
+                }
+            }
+            Err(e) => {
+                for part in Part::BOTH.iter() {
+                    outcomes.push(Outcome {
+                        day: solution.day(),
+                        part: *part,
+                        status: Status::Fail(e.to_string()),
+                        time: Duration::default(),
+                    });
+                }
+            }
+        }
+    }
+    Report { outcomes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Fake;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers =
+            Answers::parse("# comment\n\n2 1 3085697\n8 2 ## \\n#\\\\\n 4  2   1991 \n").unwrap();
+        assert_eq!(answers.get(2, Part::One), Some("3085697"));
+        assert_eq!(answers.get(8, Part::Two), Some("##\n#\\"));
+        assert_eq!(answers.get(4, Part::Two), Some("1991"));
+        assert_eq!(answers.get(4, Part::One), None);
+        assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+
+        let mut art = Answers::default();
+        art.insert(11, Part::Two, " ## \n#  #\n");
+        assert_eq!(art.to_string(), "11 2 \\ ##\\n#  #\n");
+        assert_eq!(Answers::parse(&art.to_string()).unwrap(), art);
+
+        assert_eq!(
+            Answers::parse("2 3 1").unwrap_err().to_string(),
+            "line 1: part must be 1 or 2"
+        );
+        assert_eq!(
+            Answers::parse("\nx 1 1").unwrap_err().to_string(),
+            "line 2: invalid day"
+        );
+        assert_eq!(
+            Answers::parse("2 1").unwrap_err().to_string(),
+            "line 1: missing answer"
+        );
+        assert_eq!(
+            Answers::parse("2 1 5\n2 1 5").unwrap_err().to_string(),
+            "line 2: duplicate answer"
+        );
+    }
+
+    #[test]
+    fn test_check_part() {
+        let status = |input: &str, part: Part, expected: Option<&str>| {
+            check_part(&Fake, input, part, expected).status
+        };
+        assert_eq!(status("1, 2", Part::One, Some("3")), Status::Pass);
+        // Trailing whitespace doesn't matter.
+        assert_eq!(status("1,2", Part::Two, Some("#\n##")), Status::Pass);
+        assert_eq!(
+            status("1,2", Part::One, Some("4")),
+            Status::Mismatch {
+                expected: "4".to_string(),
+                actual: "3".to_string()
+            }
+        );
+        assert_eq!(
+            status("1,2", Part::One, None),
+            Status::Unknown {
+                actual: "3".to_string()
+            }
+        );
+        assert_eq!(
+            status("1,x", Part::One, Some("3")),
+            Status::Fail("not a number".to_string())
+        );
+        assert_eq!(
+            status("0,1", Part::One, Some("1")),
+            Status::Fail("zero".to_string())
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report {
+            outcomes: vec![
+                check_part(&Fake, "1,2", Part::One, Some("3")),
+                check_part(&Fake, "1,2", Part::Two, None),
+            ],
+        };
+        assert!(report.ok());
+        let text = report.to_string();
+        assert!(text.contains("day 99 part 1  PASS"));
+        assert!(text.contains("day 99 part 2  UNKNOWN"));
+        assert!(text.contains("1 passed, 0 mismatched, 0 failed, 1 unknown"));
+
+        let report = Report {
+            outcomes: vec![check_part(&Fake, "1,2", Part::One, Some("4"))],
+        };
+        assert!(!report.ok());
+    }
+}
@@ -0,0 +1,410 @@
+//! Space Image Format, from day 8.
+//!
+//! An image is a single line of digits split into layers of width x height pixels. Each digit is
+//! a pixel: 0 black, 1 white, 2 transparent. The visible image has, at each position, the first
+//! pixel that isn't transparent, going through the layers from the top.
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::ocr;
+use std::fmt;
+use std::str::FromStr;
+
+pub mod export;
+pub mod png;
+pub mod stats;
+
+pub use stats::{Checksum, Histogram};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pixel {
+    Black,
+    White,
+    Transparent,
+}
+
+impl Pixel {
+    pub const ALL: [Pixel; 3] = [Pixel::Black, Pixel::White, Pixel::Transparent];
+
+    /// The pixel a digit stands for, None if it isn't 0, 1 or 2.
+    pub fn from_digit(c: char) -> Option<Pixel> {
+        match c {
+            '0' => Some(Pixel::Black),
+            '1' => Some(Pixel::White),
+            '2' => Some(Pixel::Transparent),
+            _ => None,
+        }
+    }
+
+    pub fn digit(self) -> char {
+        match self {
+            Pixel::Black => '0',
+            Pixel::White => '1',
+            Pixel::Transparent => '2',
+        }
+    }
+}
+
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Pixel::Black => "black",
+            Pixel::White => "white",
+            Pixel::Transparent => "transparent",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Parses a digit or a color name.
+impl FromStr for Pixel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Pixel> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(pixel) = Pixel::from_digit(c) {
+                return Ok(pixel);
+            }
+        }
+        Pixel::ALL
+            .iter()
+            .find(|p| p.to_string() == s)
+            .copied()
+            .ok_or_else(|| Error::parse(format!("unknown pixel {:?}", s)))
+    }
+}
+
+/// One layer of pixels, row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    width: usize,
+    height: usize,
+    pixels: Vec<Pixel>,
+}
+
+impl Layer {
+    /// Panics unless there are width x height pixels.
+    pub fn new(width: usize, height: usize, pixels: Vec<Pixel>) -> Layer {
+        assert_eq!(pixels.len(), width * height, "wrong number of pixels");
+        Layer {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Pixel] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Pixel {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Pixel]> {
+        self.pixels.chunks(self.width)
+    }
+
+    pub fn count(&self, pixel: Pixel) -> usize {
+        self.pixels.iter().filter(|p| **p == pixel).count()
+    }
+
+    /// This layer with `below` showing through its transparent pixels.
+    pub fn over(&self, below: &Layer) -> Layer {
+        let pixels = self
+            .pixels
+            .iter()
+            .zip(below.pixels.iter())
+            .map(|(a, b)| if *a == Pixel::Transparent { *b } else { *a })
+            .collect();
+        Layer::new(self.width, self.height, pixels)
+    }
+
+    /// Renders white pixels as '#', others as ' ', one line per row.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for row in self.rows() {
+            out.extend(
+                row.iter()
+                    .map(|p| if *p == Pixel::White { '#' } else { ' ' }),
+            );
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Reads the letters drawn in white pixels.
+    pub fn text(&self) -> ocr::Reading {
+        let rows: Vec<Vec<bool>> = self
+            .rows()
+            .map(|row| row.iter().map(|p| *p == Pixel::White).collect())
+            .collect();
+        ocr::read(&rows)
+    }
+
+    /// Renders white pixels as filled blocks for a terminal, two rows per line using half
+    /// blocks so that pixels come out roughly square.
+    pub fn blocks(&self) -> String {
+        let white = |x: usize, y: usize| y < self.height && self.get(x, y) == Pixel::White;
+        let mut out = String::new();
+        for y in (0..self.height).step_by(2) {
+            for x in 0..self.width {
+                out.push(match (white(x, y), white(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Reads the single line of an image, returns its line number and pixels.
+fn parse_pixels(input: &str) -> Result<(usize, Vec<Pixel>)> {
+    // Make sure there's exactly one line, with only valid pixels.
+    let lines: Vec<(usize, &str)> = input::lines(input).collect();
+    let (n, line) = match lines.as_slice() {
+        [line] => *line,
+        [] => return Err(Error::parse("image is empty")),
+        [_, (n, _), ..] => return Err(Error::at_line(*n, "expected a single line")),
+    };
+    let pixels = line
+        .chars()
+        .map(|c| {
+            Pixel::from_digit(c).ok_or_else(|| Error::at_line(n, format!("invalid pixel {:?}", c)))
+        })
+        .collect::<Result<Vec<Pixel>>>()?;
+    Ok((n, pixels))
+}
+
+/// Sizes, as (width, height), that split `pixels` into whole layers.
+pub fn sizes(pixels: usize) -> Vec<(usize, usize)> {
+    let divisors: Vec<usize> = (1..=pixels).filter(|d| pixels.is_multiple_of(*d)).collect();
+    let mut sizes = Vec::new();
+    for area in divisors.iter() {
+        for w in divisors.iter().filter(|w| area.is_multiple_of(**w)) {
+            sizes.push((*w, area / w));
+        }
+    }
+    sizes
+}
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    layers: Vec<Layer>,
+}
+
+impl Image {
+    /// Parses a single line of digits into layers of the given size.
+    pub fn parse(input: &str, width: usize, height: usize) -> Result<Image> {
+        if width == 0 || height == 0 {
+            return Err(Error::parse(format!("invalid size {}x{}", width, height)));
+        }
+        let (n, pixels) = parse_pixels(input)?;
+        if !pixels.len().is_multiple_of(width * height) {
+            return Err(Error::at_line(
+                n,
+                format!(
+                    "{} pixels is not a whole number of {}x{} layers",
+                    pixels.len(),
+                    width,
+                    height
+                ),
+            ));
+        }
+        Ok(Image::from_pixels(&pixels, width, height))
+    }
+
+    /// Like `parse`, but works out the size from the data when `width` or `height` is None.
+    /// Tries every size that makes whole layers, and picks the only one whose visible image is
+    /// fully opaque and reads as text.
+    pub fn infer(input: &str, width: Option<usize>, height: Option<usize>) -> Result<Image> {
+        if let (Some(width), Some(height)) = (width, height) {
+            return Image::parse(input, width, height);
+        }
+        let (_, pixels) = parse_pixels(input)?;
+        let readable: Vec<Image> = sizes(pixels.len())
+            .into_iter()
+            .filter(|(w, h)| width.is_none_or(|x| x == *w) && height.is_none_or(|x| x == *h))
+            .map(|(w, h)| Image::from_pixels(&pixels, w, h))
+            .filter(|img| {
+                let merged = img.merged();
+                let reading = merged.text();
+                merged.count(Pixel::Transparent) == 0
+                    && reading.unknown.is_empty()
+                    && !reading.text.is_empty()
+            })
+            .collect();
+        match readable.as_slice() {
+            [img] => Ok(img.clone()),
+            [] => Err(Error::parse(format!(
+                "can't work out the size of {} pixels: no size shows text",
+                pixels.len()
+            ))),
+            _ => {
+                let found: Vec<String> = readable
+                    .iter()
+                    .map(|img| format!("{}x{}", img.width, img.height))
+                    .collect();
+                Err(Error::parse(format!(
+                    "can't work out the size of {} pixels: could be {}",
+                    pixels.len(),
+                    found.join(", ")
+                )))
+            }
+        }
+    }
+
+    /// Panics unless the pixels are a whole number of layers.
+    fn from_pixels(pixels: &[Pixel], width: usize, height: usize) -> Image {
+        let layers = pixels
+            .chunks(width * height)
+            .map(|chunk| Layer::new(width, height, chunk.to_vec()))
+            .collect();
+        Image {
+            width,
+            height,
+            layers,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    pub fn layer(&self, i: usize) -> &Layer {
+        &self.layers[i]
+    }
+
+    /// Index of the first layer with the fewest pixels of a color.
+    pub fn fewest(&self, pixel: Pixel) -> usize {
+        (0..self.layers.len())
+            .min_by_key(|i| self.layers[*i].count(pixel))
+            .expect("images have at least one layer")
+    }
+
+    /// The day 8 part 1 check: on the layer with fewest black pixels, the number of white
+    /// pixels times the number of transparent ones.
+    pub fn checksum(&self) -> usize {
+        Checksum::part1().apply(self)
+    }
+
+    /// The visible image, all layers stacked. Transparent where every layer is.
+    pub fn merged(&self) -> Layer {
+        let mut merged = self.layers[0].clone();
+        for layer in self.layers[1..].iter() {
+            merged = merged.over(layer);
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let img = Image::parse("0222112222120000\n", 2, 2).unwrap();
+        assert_eq!(img.layers().len(), 4);
+        assert_eq!(img.layer(1).get(0, 0), Pixel::White);
+        assert_eq!(img.merged().render(), " #\n# \n");
+        // A single layer merges to itself.
+        assert_eq!(
+            Image::parse("0110", 2, 2).unwrap().merged().render(),
+            " #\n# \n"
+        );
+
+        let err = |input: &str| Image::parse(input, 2, 2).unwrap_err().to_string();
+        assert_eq!(err(""), "image is empty");
+        assert_eq!(err("0110\n0110"), "line 2: expected a single line");
+        assert_eq!(err("01x0"), "line 1: invalid pixel 'x'");
+        assert_eq!(err("0130"), "line 1: invalid pixel '3'");
+        assert_eq!(
+            err("011"),
+            "line 1: 3 pixels is not a whole number of 2x2 layers"
+        );
+        assert!(Image::parse("0110", 0, 2).is_err());
+    }
+
+    #[test]
+    fn test_checksum() {
+        // The layer with no black pixels has four white and two transparent.
+        let img = Image::parse("000012111122", 3, 2).unwrap();
+        assert_eq!(img.fewest(Pixel::Black), 1);
+        assert_eq!(img.checksum(), 8);
+        // Ties go to the first layer.
+        assert_eq!(img.fewest(Pixel::White), 0);
+    }
+
+    #[test]
+    fn test_merged() {
+        let img = Image::parse("2221 0211 1102", 2, 2).unwrap_err();
+        assert_eq!(img.to_string(), "line 1: invalid pixel ' '");
+        let img = Image::parse("222102111102", 2, 2).unwrap();
+        let merged = img.merged();
+        assert_eq!(
+            merged.pixels(),
+            &[Pixel::Black, Pixel::White, Pixel::White, Pixel::White]
+        );
+        let clear = Image::parse("2222", 2, 2).unwrap().merged();
+        assert_eq!(clear.count(Pixel::Transparent), 4);
+    }
+
+    #[test]
+    fn test_infer() {
+        assert_eq!(
+            sizes(4),
+            vec![(1, 1), (1, 2), (2, 1), (1, 4), (2, 2), (4, 1)]
+        );
+        let input = std::fs::read_to_string("inputs/day8.txt").unwrap();
+        let img = Image::infer(&input, None, None).unwrap();
+        assert_eq!((img.width(), img.height()), (25, 6));
+        let img = Image::infer(&input, None, Some(6)).unwrap();
+        assert_eq!(img.width(), 25);
+        assert_eq!(
+            Image::infer(&input, Some(24), None)
+                .unwrap_err()
+                .to_string(),
+            "can't work out the size of 15000 pixels: no size shows text"
+        );
+        assert!(Image::infer(&input, Some(24), Some(6)).is_err());
+        assert_eq!("black".parse::<Pixel>().unwrap(), Pixel::Black);
+        assert_eq!("2".parse::<Pixel>().unwrap(), Pixel::Transparent);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let layer = Image::parse("101011100", 3, 3).unwrap().merged();
+        assert_eq!(layer.blocks(), "▀▄█\n▀  \n");
+    }
+
+    #[test]
+    fn test_text() {
+        let input = std::fs::read_to_string("inputs/day8.txt").unwrap();
+        let img = Image::parse(&input, 25, 6).unwrap();
+        assert_eq!(img.merged().text().into_result().unwrap(), "FGJUZ");
+    }
+}
@@ -0,0 +1,99 @@
+//! Image file formats for layers: PBM and PGM (plain text Netpbm) and PNG.
+//!
+//! Every exporter takes a scale, the size in image pixels of one layer pixel, since a 25x6
+//! message is hard to read at its natural size.
+
+use super::{png, Layer, Pixel};
+
+/// Gray level of each pixel, 0 black to 255 white. Transparent pixels are mid gray.
+fn gray(pixel: Pixel) -> u8 {
+    match pixel {
+        Pixel::Black => 0,
+        Pixel::White => 255,
+        Pixel::Transparent => 128,
+    }
+}
+
+/// Scaled rows of values, one per image pixel.
+fn scaled<T: Copy, F: Fn(Pixel) -> T>(layer: &Layer, scale: usize, f: F) -> Vec<Vec<T>> {
+    let mut rows = Vec::with_capacity(layer.height() * scale);
+    for row in layer.rows() {
+        let scaled: Vec<T> = row
+            .iter()
+            .flat_map(|p| std::iter::repeat_n(f(*p), scale))
+            .collect();
+        for _ in 0..scale {
+            rows.push(scaled.clone());
+        }
+    }
+    rows
+}
+
+fn join<T: ToString>(rows: Vec<Vec<T>>) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let row: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        out.push_str(&row.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// Plain PBM, where 1 is black. Only white pixels are drawn white; transparent ones are black,
+/// as they are on screen.
+pub fn pbm(layer: &Layer, scale: usize) -> String {
+    let rows = scaled(layer, scale, |p| u8::from(p != Pixel::White));
+    format!(
+        "P1\n{} {}\n{}",
+        layer.width() * scale,
+        layer.height() * scale,
+        join(rows)
+    )
+}
+
+/// Plain PGM, with transparent pixels in gray.
+pub fn pgm(layer: &Layer, scale: usize) -> String {
+    let rows = scaled(layer, scale, gray);
+    format!(
+        "P2\n{} {}\n255\n{}",
+        layer.width() * scale,
+        layer.height() * scale,
+        join(rows)
+    )
+}
+
+/// Grayscale PNG, with transparent pixels in gray.
+pub fn png(layer: &Layer, scale: usize) -> Vec<u8> {
+    let pixels: Vec<u8> = scaled(layer, scale, gray).concat();
+    png::encode_gray(layer.width() * scale, layer.height() * scale, &pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sif::Image;
+
+    fn layer() -> Layer {
+        Image::parse("012210", 3, 2).unwrap().layer(0).clone()
+    }
+
+    #[test]
+    fn test_netpbm() {
+        assert_eq!(pbm(&layer(), 1), "P1\n3 2\n1 0 1\n1 0 1\n");
+        assert_eq!(
+            pbm(&layer(), 2),
+            "P1\n6 4\n1 1 0 0 1 1\n1 1 0 0 1 1\n1 1 0 0 1 1\n1 1 0 0 1 1\n"
+        );
+        assert_eq!(pgm(&layer(), 1), "P2\n3 2\n255\n0 255 128\n128 255 0\n");
+    }
+
+    #[test]
+    fn test_png() {
+        let png = png(&layer(), 3);
+        // Width and height in the header.
+        assert_eq!(png[16..24], [0, 0, 0, 9, 0, 0, 0, 6]);
+        // Six rows of a filter byte and nine pixels, uncompressed.
+        let row = [0, 0, 0, 0, 255, 255, 255, 128, 128, 128];
+        assert!(png.windows(row.len()).any(|w| w == row));
+    }
+}
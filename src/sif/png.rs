@@ -0,0 +1,120 @@
+//! Minimal PNG encoder for 8-bit grayscale images.
+//!
+//! Image data goes into zlib "stored" blocks, which aren't compressed at all. That keeps the
+//! encoder tiny, and puzzle images are small enough that the size doesn't matter.
+
+/// CRC-32 as used by PNG chunks (the zlib/Ethernet polynomial, reflected).
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes.iter() {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Adler-32, the zlib stream checksum.
+pub fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in bytes.iter() {
+        a = (a + u32::from(*byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    b << 16 | a
+}
+
+/// Wraps `data` in a zlib stream of uncompressed blocks.
+pub fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate, no preset dictionary, 32K window; the check bits make the header divisible by 31.
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        // A single empty final block.
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(u8::from(last));
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Encodes a grayscale image, one byte per pixel row by row, 0 black to 255 white. Panics
+/// unless there are width x height pixels.
+pub fn encode_gray(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    assert_eq!(pixels.len(), width * height, "wrong number of pixels");
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits, grayscale, deflate, standard filter method, not interlaced.
+    header.extend_from_slice(&[8, 0, 0, 0, 0]);
+    chunk(&mut out, b"IHDR", &header);
+
+    // Each row starts with its filter type, 0 for none.
+    let mut raw = Vec::with_capacity((width + 1) * height);
+    for row in pixels.chunks(width.max(1)).take(height) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_zlib() {
+        assert_eq!(
+            zlib_stored(b"ab"),
+            vec![0x78, 0x01, 1, 2, 0, 0xFD, 0xFF, b'a', b'b', 0x01, 0x26, 0, 0xC4]
+        );
+        assert_eq!(zlib_stored(b"")[2..7], [1, 0, 0, 0xFF, 0xFF]);
+        // Data longer than one block is split, and only the last block is final.
+        let data = vec![7; 0x10000];
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 5 + 0xFFFF + 5 + 1 + 4);
+        assert_eq!(stream[2..7], [0, 0xFF, 0xFF, 0, 0]);
+        assert_eq!(stream[2 + 5 + 0xFFFF..][..5], [1, 1, 0, 0xFE, 0xFF]);
+    }
+
+    #[test]
+    fn test_encode() {
+        let png = encode_gray(2, 1, &[0, 255]);
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(png[8..16], [0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+        assert_eq!(png[16..29], [0, 0, 0, 2, 0, 0, 0, 1, 8, 0, 0, 0, 0]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+    }
+}
@@ -0,0 +1,226 @@
+//! Pixel counts and checksums for images, and which layer each visible pixel comes from.
+
+use super::{Image, Layer, Pixel};
+use crate::error::{Error, Result};
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Number of pixels of each color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Histogram {
+    counts: [usize; 3],
+}
+
+fn index(pixel: Pixel) -> usize {
+    match pixel {
+        Pixel::Black => 0,
+        Pixel::White => 1,
+        Pixel::Transparent => 2,
+    }
+}
+
+impl Histogram {
+    pub fn of(pixels: &[Pixel]) -> Histogram {
+        let mut histogram = Histogram::default();
+        for p in pixels.iter() {
+            histogram.counts[index(*p)] += 1;
+        }
+        histogram
+    }
+
+    pub fn get(&self, pixel: Pixel) -> usize {
+        self.counts[index(pixel)]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+impl Add for Histogram {
+    type Output = Histogram;
+
+    fn add(self, other: Histogram) -> Histogram {
+        let mut sum = self;
+        for (a, b) in sum.counts.iter_mut().zip(other.counts.iter()) {
+            *a += b;
+        }
+        sum
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts: Vec<String> = Pixel::ALL
+            .iter()
+            .map(|p| format!("{} {}", p, self.get(*p)))
+            .collect();
+        f.write_str(&counts.join(", "))
+    }
+}
+
+impl Layer {
+    pub fn histogram(&self) -> Histogram {
+        Histogram::of(&self.pixels)
+    }
+}
+
+/// A checksum like day 8 part 1: find the layer with the fewest pixels of one color, and
+/// multiply its counts of two others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checksum {
+    pub fewest: Pixel,
+    pub multiply: (Pixel, Pixel),
+}
+
+impl Checksum {
+    /// Fewest black, then white times transparent.
+    pub fn part1() -> Checksum {
+        Checksum {
+            fewest: Pixel::Black,
+            multiply: (Pixel::White, Pixel::Transparent),
+        }
+    }
+
+    pub fn apply(&self, image: &Image) -> usize {
+        let histogram = image.layer(image.fewest(self.fewest)).histogram();
+        histogram.get(self.multiply.0) * histogram.get(self.multiply.1)
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}*{}",
+            self.fewest.digit(),
+            self.multiply.0.digit(),
+            self.multiply.1.digit()
+        )
+    }
+}
+
+/// Parses `FEWEST:A*B`, each a pixel digit or color name, e.g. `0:1*2` or
+/// `black:white*transparent`.
+impl FromStr for Checksum {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Checksum> {
+        let err = || Error::parse(format!("expected FEWEST:A*B, got {:?}", s));
+        let (fewest, product) = s.split_once(':').ok_or_else(err)?;
+        let (a, b) = product.split_once('*').ok_or_else(err)?;
+        Ok(Checksum {
+            fewest: fewest.parse()?,
+            multiply: (a.parse()?, b.parse()?),
+        })
+    }
+}
+
+impl Image {
+    /// Pixel counts of each layer, top first.
+    pub fn histograms(&self) -> Vec<Histogram> {
+        self.layers.iter().map(|l| l.histogram()).collect()
+    }
+
+    /// Pixel counts over all layers.
+    pub fn histogram(&self) -> Histogram {
+        self.histograms()
+            .into_iter()
+            .fold(Histogram::default(), |a, b| a + b)
+    }
+
+    /// For each pixel, row by row, the index of the top layer that isn't transparent there,
+    /// which decides its visible color. None where every layer is transparent.
+    pub fn depths(&self) -> Vec<Option<usize>> {
+        (0..self.width * self.height)
+            .map(|i| {
+                self.layers
+                    .iter()
+                    .position(|l| l.pixels[i] != Pixel::Transparent)
+            })
+            .collect()
+    }
+
+    /// `depths` as text, one line per row of space separated numbers, lined up. '.' where no
+    /// layer decides.
+    pub fn render_depths(&self) -> String {
+        let depths = self.depths();
+        let width = depths
+            .iter()
+            .flatten()
+            .max()
+            .map_or(1, |d| d.to_string().len());
+        let mut out = String::new();
+        for row in depths.chunks(self.width) {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|d| match d {
+                    Some(d) => format!("{:>w$}", d, w = width),
+                    None => format!("{:>w$}", ".", w = width),
+                })
+                .collect();
+            out.push_str(&cells.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        Image::parse("222102111102", 2, 2).unwrap()
+    }
+
+    #[test]
+    fn test_histogram() {
+        let img = image();
+        let histograms: Vec<String> = img.histograms().iter().map(|h| h.to_string()).collect();
+        assert_eq!(
+            histograms,
+            vec![
+                "black 0, white 1, transparent 3",
+                "black 1, white 2, transparent 1",
+                "black 1, white 2, transparent 1"
+            ]
+        );
+        let total = img.histogram();
+        assert_eq!(total.get(Pixel::White), 5);
+        assert_eq!(total.total(), 12);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let img = image();
+        assert_eq!(Checksum::part1().apply(&img), 3);
+        assert_eq!(Checksum::part1().apply(&img), img.checksum());
+        let fewest_white: Checksum = "white:black*transparent".parse().unwrap();
+        assert_eq!(fewest_white.to_string(), "1:0*2");
+        assert_eq!(fewest_white.apply(&img), 0);
+        let squared: Checksum = "2:1*1".parse().unwrap();
+        assert_eq!(squared.apply(&img), 4);
+        assert_eq!(
+            "0:1".parse::<Checksum>().unwrap_err().to_string(),
+            "expected FEWEST:A*B, got \"0:1\""
+        );
+        assert_eq!(
+            "0:1*purple".parse::<Checksum>().unwrap_err().to_string(),
+            "unknown pixel \"purple\""
+        );
+    }
+
+    #[test]
+    fn test_depths() {
+        let img = image();
+        assert_eq!(img.depths(), vec![Some(1), Some(2), Some(1), Some(0)]);
+        assert_eq!(img.render_depths(), "1 2\n1 0\n");
+        let clear = Image::parse("22222222", 2, 2).unwrap();
+        assert_eq!(clear.render_depths(), ". .\n. .\n");
+        // Lined up when some depths are wider.
+        let deep = Image::parse(&format!("2122{}1110", "2".repeat(36)), 2, 2).unwrap();
+        assert_eq!(deep.render_depths(), "10  0\n10 10\n");
+    }
+}
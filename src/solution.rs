@@ -0,0 +1,186 @@
+//! Common interface for puzzle solutions, so one binary can run any day.
+
+use crate::days;
+use crate::error::Result;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Solution for one day's puzzle.
+pub trait Solution {
+    /// Puzzle input after parsing.
+    type Input;
+
+    fn day(&self) -> u32;
+
+    /// Parses the puzzle input text.
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> String;
+
+    fn part2(&self, input: &Self::Input) -> String;
+
+    /// Input file used when none is given.
+    fn default_input(&self) -> String {
+        format!("inputs/day{}.txt", self.day())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(n: u32) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Object-safe view of a `Solution`, so different days can be kept in one list.
+pub trait Runner {
+    fn day(&self) -> u32;
+
+    fn default_input(&self) -> String;
+
+    /// Parses input and solves the given parts, returning answers in the same order.
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<String>>;
+
+    /// Runs parse, part 1 and part 2 once each, returning how long each phase took.
+    fn time_phases(&self, input: &str) -> Result<[Duration; 3]>;
+}
+
+impl<S: Solution> Runner for S {
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn default_input(&self) -> String {
+        Solution::default_input(self)
+    }
+
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<String>> {
+        let parsed = self.parse(input)?;
+        Ok(parts
+            .iter()
+            .map(|part| match part {
+                Part::One => self.part1(&parsed),
+                Part::Two => self.part2(&parsed),
+            })
+            .collect())
+    }
+
+    fn time_phases(&self, input: &str) -> Result<[Duration; 3]> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+        let start = Instant::now();
+        self.part1(&parsed);
+        let part1 = start.elapsed();
+        let start = Instant::now();
+        self.part2(&parsed);
+        let part2 = start.elapsed();
+        Ok([parse, part1, part2])
+    }
+}
+
+/// All implemented days, in order.
+pub fn all() -> Vec<Box<dyn Runner>> {
+    vec![
+        Box::new(days::day2::Day2),
+        Box::new(days::day3::Day3),
+        Box::new(days::day4::Day4),
+        Box::new(days::day5::Day5),
+        Box::new(days::day6::Day6),
+        Box::new(days::day7::Day7),
+        Box::new(days::day8::Day8),
+        Box::new(days::day9::Day9),
+        Box::new(days::day10::Day10),
+        Box::new(days::day11::Day11),
+        Box::new(days::day12::Day12),
+        Box::new(days::day13::Day13),
+        Box::new(days::day14::Day14),
+    ]
+}
+
+/// Solution for the given day, if there is one.
+pub fn find(day: u32) -> Option<Box<dyn Runner>> {
+    all().into_iter().find(|s| s.day() == day)
+}
+
+/// Stand-in solution for tests of code that runs solutions. Sums comma-separated numbers in
+/// part 1 and draws them in part 2. Panics on zeroes.
+#[cfg(test)]
+pub(crate) struct Fake;
+
+#[cfg(test)]
+impl Solution for Fake {
+    type Input = Vec<u32>;
+
+    fn day(&self) -> u32 {
+        99
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        input
+            .split(',')
+            .map(|x| {
+                x.trim()
+                    .parse()
+                    .map_err(|_| crate::error::Error::parse("not a number"))
+            })
+            .collect()
+    }
+
+    fn part1(&self, input: &Vec<u32>) -> String {
+        assert!(!input.contains(&0), "zero");
+        input.iter().sum::<u32>().to_string()
+    }
+
+    fn part2(&self, input: &Vec<u32>) -> String {
+        input
+            .iter()
+            .map(|n| "#".repeat(*n as usize) + " ")
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<u32> = all().iter().map(|s| s.day()).collect();
+        assert_eq!(days, vec![2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(find(4).unwrap().default_input(), "inputs/day4.txt");
+        assert!(find(1).is_none());
+    }
+
+    #[test]
+    fn test_part() {
+        assert_eq!(Part::from_number(2), Some(Part::Two));
+        assert_eq!(Part::from_number(3), None);
+        assert_eq!(Part::One.to_string(), "1");
+    }
+}
@@ -0,0 +1,367 @@
+//! Rooted trees of named nodes, like the day 6 orbit map.
+//!
+//! Names are interned: each gets a `NodeId` indexing into flat vectors. Depths are computed once
+//! when the tree is built, in linear time, along with a binary lifting table so that lowest
+//! common ancestor and distance queries take O(log depth).
+
+use std::collections::HashMap;
+use std::fmt;
+
+pub mod export;
+pub mod validate;
+
+pub type NodeId = usize;
+
+/// Why edges don't form a tree (or forest).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeError {
+    /// A node was given two parents.
+    DuplicateParent {
+        child: String,
+        first: String,
+        second: String,
+    },
+    /// Nodes that are their own ancestors, in parent order.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TreeError::DuplicateParent {
+                child,
+                first,
+                second,
+            } => write!(f, "{} has two parents: {} and {}", child, first, second),
+            TreeError::Cycle(names) => write!(f, "cycle: {}", names.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for TreeError {}
+
+/// A forest, really: there can be several roots.
+#[derive(Debug, Clone)]
+pub struct Tree {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    parent: Vec<Option<NodeId>>,
+    children: Vec<Vec<NodeId>>,
+    depth: Vec<usize>,
+    root: Vec<NodeId>,
+    /// up[k][v] is the 2^k-th ancestor of v, or the root if there are fewer.
+    up: Vec<Vec<NodeId>>,
+}
+
+impl Tree {
+    /// Builds a tree from (parent, child) pairs.
+    pub fn from_edges<'a, I>(edges: I) -> Result<Tree, TreeError>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut tree = Tree {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            parent: Vec::new(),
+            children: Vec::new(),
+            depth: Vec::new(),
+            root: Vec::new(),
+            up: Vec::new(),
+        };
+        for (parent, child) in edges {
+            let p = tree.intern(parent);
+            let c = tree.intern(child);
+            if let Some(first) = tree.parent[c] {
+                return Err(TreeError::DuplicateParent {
+                    child: child.to_string(),
+                    first: tree.names[first].clone(),
+                    second: parent.to_string(),
+                });
+            }
+            tree.parent[c] = Some(p);
+            tree.children[p].push(c);
+        }
+        tree.index()?;
+        Ok(tree)
+    }
+
+    fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.parent.push(None);
+        self.children.push(Vec::new());
+        id
+    }
+
+    /// Fills in depths, roots and the lifting table. Fails if some nodes aren't reachable from
+    /// a root, which means they're on or below a cycle.
+    fn index(&mut self) -> Result<(), TreeError> {
+        let n = self.names.len();
+        self.depth = vec![usize::MAX; n];
+        self.root = vec![0; n];
+        let mut stack: Vec<NodeId> = (0..n).filter(|v| self.parent[*v].is_none()).collect();
+        for v in stack.iter() {
+            self.depth[*v] = 0;
+            self.root[*v] = *v;
+        }
+        while let Some(v) = stack.pop() {
+            for &c in self.children[v].iter() {
+                self.depth[c] = self.depth[v] + 1;
+                self.root[c] = self.root[v];
+                stack.push(c);
+            }
+        }
+        if let Some(v) = (0..n).find(|v| self.depth[*v] == usize::MAX) {
+            return Err(TreeError::Cycle(self.cycle_from(v)));
+        }
+
+        let max_depth = self.depth.iter().copied().max().unwrap_or(0);
+        let levels = (usize::BITS - max_depth.leading_zeros()).max(1) as usize;
+        let first: Vec<NodeId> = (0..n).map(|v| self.parent[v].unwrap_or(v)).collect();
+        self.up = vec![first];
+        for k in 1..levels {
+            let prev = &self.up[k - 1];
+            let next = (0..n).map(|v| prev[prev[v]]).collect();
+            self.up.push(next);
+        }
+        Ok(())
+    }
+
+    /// Follows parents from `v` until a node repeats, returns the repeating part.
+    fn cycle_from(&self, v: NodeId) -> Vec<String> {
+        let mut seen = HashMap::new();
+        let mut path = Vec::new();
+        let mut node = v;
+        while !seen.contains_key(&node) {
+            seen.insert(node, path.len());
+            path.push(node);
+            node = self.parent[node].expect("unreachable node has a parent");
+        }
+        path[seen[&node]..]
+            .iter()
+            .map(|v| self.names[*v].clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parent[id]
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.children[id]
+    }
+
+    /// Nodes without a parent, in the order they first appeared.
+    pub fn roots(&self) -> Vec<NodeId> {
+        (0..self.len())
+            .filter(|v| self.parent[*v].is_none())
+            .collect()
+    }
+
+    /// Number of edges from the node up to its root.
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.depth[id]
+    }
+
+    /// Sum of all depths: the number of direct and indirect orbits in day 6.
+    pub fn total_depth(&self) -> usize {
+        self.depth.iter().sum()
+    }
+
+    /// The ancestor `k` levels up, if there is one.
+    pub fn ancestor(&self, mut id: NodeId, k: usize) -> Option<NodeId> {
+        if k > self.depth[id] {
+            return None;
+        }
+        for (level, up) in self.up.iter().enumerate() {
+            if k >> level & 1 == 1 {
+                id = up[id];
+            }
+        }
+        Some(id)
+    }
+
+    /// Lowest common ancestor, None if the nodes are in different trees.
+    pub fn lca(&self, a: NodeId, b: NodeId) -> Option<NodeId> {
+        if self.root[a] != self.root[b] {
+            return None;
+        }
+        let (mut a, mut b) = if self.depth[a] >= self.depth[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        a = self.ancestor(a, self.depth[a] - self.depth[b])?;
+        if a == b {
+            return Some(a);
+        }
+        for up in self.up.iter().rev() {
+            if up[a] != up[b] {
+                a = up[a];
+                b = up[b];
+            }
+        }
+        self.parent[a]
+    }
+
+    /// Number of edges between two nodes, None if they're in different trees.
+    pub fn distance(&self, a: NodeId, b: NodeId) -> Option<usize> {
+        let lca = self.lca(a, b)?;
+        Some(self.depth[a] + self.depth[b] - 2 * self.depth[lca])
+    }
+
+    /// Like `distance`, by name. None if either name is unknown.
+    pub fn distance_between(&self, a: &str, b: &str) -> Option<usize> {
+        self.distance(self.id(a)?, self.id(b)?)
+    }
+
+    /// Nodes from `a` up to the common ancestor and down to `b`, both ends included.
+    pub fn path(&self, a: NodeId, b: NodeId) -> Option<Vec<NodeId>> {
+        let lca = self.lca(a, b)?;
+        let up = |mut v: NodeId| {
+            let mut nodes = vec![v];
+            while v != lca {
+                v = self.up[0][v];
+                nodes.push(v);
+            }
+            nodes
+        };
+        let mut path = up(a);
+        let mut down = up(b);
+        down.pop();
+        path.extend(down.into_iter().rev());
+        Some(path)
+    }
+
+    /// Number of nodes in each node's subtree, itself included.
+    pub fn subtree_sizes(&self) -> Vec<usize> {
+        let mut order: Vec<NodeId> = (0..self.len()).collect();
+        order.sort_by_key(|v| std::cmp::Reverse(self.depth[*v]));
+        let mut sizes = vec![1; self.len()];
+        // Deepest first, so children are done before their parents.
+        for v in order {
+            if let Some(p) = self.parent[v] {
+                sizes[p] += sizes[v];
+            }
+        }
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from day 6 part 2.
+    fn example() -> Tree {
+        let edges = "COM)B B)C C)D D)E E)F B)G G)H D)I E)J J)K K)L K)YOU I)SAN";
+        Tree::from_edges(edges.split(' ').map(|e| e.split_once(')').unwrap())).unwrap()
+    }
+
+    /// Depth found by walking parents.
+    fn slow_depth(tree: &Tree, mut v: NodeId) -> usize {
+        let mut depth = 0;
+        while let Some(p) = tree.parent(v) {
+            depth += 1;
+            v = p;
+        }
+        depth
+    }
+
+    #[test]
+    fn test_depths() {
+        let tree = example();
+        assert_eq!(tree.len(), 14);
+        assert_eq!(tree.roots(), vec![tree.id("COM").unwrap()]);
+        assert_eq!(tree.depth(tree.id("L").unwrap()), 7);
+        for v in 0..tree.len() {
+            assert_eq!(tree.depth(v), slow_depth(&tree, v));
+        }
+        // Part 1 example, without YOU and SAN: 42. YOU and SAN add 7 and 5.
+        assert_eq!(tree.total_depth(), 42 + 7 + 5);
+        let sizes = tree.subtree_sizes();
+        assert_eq!(sizes[tree.id("COM").unwrap()], 14);
+        assert_eq!(sizes[tree.id("K").unwrap()], 3);
+        assert_eq!(sizes[tree.id("SAN").unwrap()], 1);
+    }
+
+    #[test]
+    fn test_lca() {
+        let tree = example();
+        let id = |name| tree.id(name).unwrap();
+        assert_eq!(tree.ancestor(id("L"), 3), Some(id("E")));
+        assert_eq!(tree.ancestor(id("L"), 7), Some(id("COM")));
+        assert_eq!(tree.ancestor(id("L"), 8), None);
+        assert_eq!(tree.lca(id("YOU"), id("SAN")), Some(id("D")));
+        assert_eq!(tree.lca(id("H"), id("F")), Some(id("B")));
+        assert_eq!(tree.lca(id("L"), id("E")), Some(id("E")));
+        assert_eq!(tree.lca(id("C"), id("C")), Some(id("C")));
+        // Orbital transfers between the objects YOU and SAN orbit.
+        assert_eq!(tree.distance_between("K", "I"), Some(4));
+        assert_eq!(tree.distance_between("YOU", "nope"), None);
+
+        let names =
+            |path: Vec<NodeId>| -> Vec<&str> { path.iter().map(|v| tree.name(*v)).collect() };
+        assert_eq!(
+            names(tree.path(id("YOU"), id("SAN")).unwrap()),
+            vec!["YOU", "K", "J", "E", "D", "I", "SAN"]
+        );
+        assert_eq!(names(tree.path(id("C"), id("C")).unwrap()), vec!["C"]);
+        assert_eq!(
+            names(tree.path(id("COM"), id("C")).unwrap()),
+            vec!["COM", "B", "C"]
+        );
+    }
+
+    #[test]
+    fn test_forest() {
+        let tree = Tree::from_edges(vec![("A", "B"), ("X", "Y"), ("B", "C")]).unwrap();
+        assert_eq!(tree.roots().len(), 2);
+        assert_eq!(tree.distance_between("A", "C"), Some(2));
+        assert_eq!(tree.distance_between("C", "Y"), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Tree::from_edges(vec![("A", "B"), ("C", "B")])
+                .unwrap_err()
+                .to_string(),
+            "B has two parents: A and C"
+        );
+        assert_eq!(
+            Tree::from_edges(vec![("R", "A"), ("A", "B"), ("B", "C"), ("C", "B")]).unwrap_err(),
+            TreeError::DuplicateParent {
+                child: "B".to_string(),
+                first: "A".to_string(),
+                second: "C".to_string()
+            }
+        );
+        assert_eq!(
+            Tree::from_edges(vec![("A", "B"), ("B", "C"), ("C", "A"), ("C", "D")])
+                .unwrap_err()
+                .to_string(),
+            "cycle: A -> C -> B"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Text renderings of trees: Graphviz DOT, and an indented outline.
+
+use super::{NodeId, Tree};
+use std::collections::HashSet;
+
+/// Quotes a name for DOT.
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Renders the tree as a Graphviz digraph with edges from parent to child. Nodes and edges on
+/// `highlight`, a path as returned by `Tree::path`, are drawn in red.
+pub fn dot(tree: &Tree, highlight: &[NodeId]) -> String {
+    let path_edges: HashSet<(NodeId, NodeId)> = highlight
+        .windows(2)
+        .flat_map(|w| vec![(w[0], w[1]), (w[1], w[0])])
+        .collect();
+
+    let mut out = String::from("digraph tree {\n    rankdir=LR;\n    node [shape=box];\n");
+    for v in highlight.iter() {
+        out.push_str(&format!(
+            "    {} [color=red, fontcolor=red];\n",
+            quote(tree.name(*v))
+        ));
+    }
+    for v in 0..tree.len() {
+        if let Some(p) = tree.parent(v) {
+            let style = if path_edges.contains(&(p, v)) {
+                " [color=red, penwidth=2]"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    {} -> {}{};\n",
+                quote(tree.name(p)),
+                quote(tree.name(v)),
+                style
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// One line per node, indented two spaces per level, with its subtree size and depth. Children
+/// are listed in input order.
+pub fn indented(tree: &Tree) -> String {
+    let sizes = tree.subtree_sizes();
+    let mut out = String::new();
+    // Explicit stack, since orbit maps can be hundreds of levels deep.
+    let mut stack: Vec<NodeId> = tree.roots().into_iter().rev().collect();
+    while let Some(v) = stack.pop() {
+        let depth = tree.depth(v);
+        out.push_str(&format!(
+            "{}{} (size {}, depth {})\n",
+            "  ".repeat(depth),
+            tree.name(v),
+            sizes[v],
+            depth
+        ));
+        stack.extend(tree.children(v).iter().rev());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Tree {
+        Tree::from_edges(vec![
+            ("COM", "B"),
+            ("B", "C"),
+            ("B", "YOU"),
+            ("COM", "SAN"),
+            ("\"X\"", "Y"),
+            ("Z", "Z2"),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dot() {
+        let tree = tree();
+        let path = tree
+            .path(tree.id("YOU").unwrap(), tree.id("SAN").unwrap())
+            .unwrap();
+        let dot = dot(&tree, &path);
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    \"YOU\" [color=red, fontcolor=red];\n"));
+        assert!(dot.contains("    \"COM\" [color=red, fontcolor=red];\n"));
+        assert!(!dot.contains("    \"C\" [color=red"));
+        assert!(dot.contains("    \"B\" -> \"YOU\" [color=red, penwidth=2];\n"));
+        assert!(dot.contains("    \"COM\" -> \"SAN\" [color=red, penwidth=2];\n"));
+        assert!(dot.contains("    \"B\" -> \"C\";\n"));
+        assert!(dot.contains("    \"\\\"X\\\"\" -> \"Y\";\n"));
+
+        let plain = super::dot(&tree, &[]);
+        assert!(!plain.contains("red"));
+    }
+
+    #[test]
+    fn test_indented() {
+        assert_eq!(
+            indented(&tree()),
+            "COM (size 5, depth 0)\n  B (size 3, depth 1)\n    C (size 1, depth 2)\n    \
+             YOU (size 1, depth 2)\n  SAN (size 1, depth 1)\n\"X\" (size 2, depth 0)\n  \
+             Y (size 1, depth 1)\nZ (size 2, depth 0)\n  Z2 (size 1, depth 1)\n"
+        );
+    }
+}
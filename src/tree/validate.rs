@@ -0,0 +1,233 @@
+//! Checks (parent, child) edges before building a tree, reporting every problem at once rather
+//! than stopping at the first.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// A node given more than one parent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateParent {
+    pub child: String,
+    /// All parents given, in input order.
+    pub parents: Vec<String>,
+}
+
+/// Nodes connected to each other by edges, ignoring direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub size: usize,
+    /// Nodes without a parent. Empty if the component is a cycle with branches.
+    pub roots: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    /// Nodes without a parent, in input order.
+    pub roots: Vec<String>,
+    pub duplicate_parents: Vec<DuplicateParent>,
+    /// Each cycle in parent order, starting from its first node in input order. Only the first
+    /// parent of each node is followed.
+    pub cycles: Vec<Vec<String>>,
+    /// Largest first.
+    pub components: Vec<Component>,
+}
+
+impl Report {
+    /// True if the edges form a single tree.
+    pub fn is_tree(&self) -> bool {
+        self.duplicate_parents.is_empty() && self.cycles.is_empty() && self.components.len() <= 1
+    }
+
+    /// True if the edges form a tree or several: no duplicate parents or cycles.
+    pub fn is_forest(&self) -> bool {
+        self.duplicate_parents.is_empty() && self.cycles.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "roots: {}", list(&self.roots))?;
+        writeln!(f, "components: {}", self.components.len())?;
+        if self.components.len() > 1 {
+            for c in self.components.iter() {
+                writeln!(f, "  {} nodes, roots: {}", c.size, list(&c.roots))?;
+            }
+        }
+        writeln!(f, "duplicate parents: {}", self.duplicate_parents.len())?;
+        for d in self.duplicate_parents.iter() {
+            writeln!(f, "  {}: {}", d.child, d.parents.join(", "))?;
+        }
+        write!(f, "cycles: {}", self.cycles.len())?;
+        for c in self.cycles.iter() {
+            write!(f, "\n  {}", c.join(" -> "))?;
+        }
+        Ok(())
+    }
+}
+
+fn list(names: &[String]) -> String {
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn find(set: &mut [usize], mut v: usize) -> usize {
+    while set[v] != v {
+        set[v] = set[set[v]];
+        v = set[v];
+    }
+    v
+}
+
+/// Checks (parent, child) edges.
+pub fn validate<'a, I>(edges: I) -> Report
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut names: Vec<&str> = Vec::new();
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut parents: Vec<Vec<usize>> = Vec::new();
+    let mut intern = |name: &'a str, names: &mut Vec<&'a str>, parents: &mut Vec<Vec<usize>>| {
+        *ids.entry(name).or_insert_with(|| {
+            names.push(name);
+            parents.push(Vec::new());
+            names.len() - 1
+        })
+    };
+    for (parent, child) in edges {
+        let p = intern(parent, &mut names, &mut parents);
+        let c = intern(child, &mut names, &mut parents);
+        parents[c].push(p);
+    }
+    let n = names.len();
+    let name = |v: &usize| names[*v].to_string();
+    let roots = (0..n)
+        .filter(|v| parents[*v].is_empty())
+        .map(|v| name(&v))
+        .collect();
+    let duplicate_parents = (0..n)
+        .filter(|v| parents[*v].len() > 1)
+        .map(|v| DuplicateParent {
+            child: name(&v),
+            parents: parents[v].iter().map(name).collect(),
+        })
+        .collect();
+
+    // Walk up from each node along first parents. A walk that runs into itself found a cycle.
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+    let mut walked = vec![usize::MAX; n];
+    for start in 0..n {
+        let mut v = start;
+        while walked[v] == usize::MAX {
+            walked[v] = start;
+            match parents[v].first() {
+                Some(p) => v = *p,
+                None => break,
+            }
+        }
+        if walked[v] == start && !parents[v].is_empty() {
+            let mut cycle = vec![v];
+            let mut u = parents[v][0];
+            while u != v {
+                cycle.push(u);
+                u = parents[u][0];
+            }
+            // Start from the node that came first in the input.
+            let first = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+            cycle.rotate_left(first);
+            cycles.push(cycle.iter().map(name).collect());
+        }
+    }
+    cycles.sort_by_key(|c| ids[c[0].as_str()]);
+
+    let mut set: Vec<usize> = (0..n).collect();
+    for (c, ps) in parents.iter().enumerate() {
+        for p in ps.iter() {
+            let (a, b) = (find(&mut set, c), find(&mut set, *p));
+            set[a] = b;
+        }
+    }
+    let mut components: HashMap<usize, Component> = HashMap::new();
+    let mut order = Vec::new();
+    for (v, ps) in parents.iter().enumerate() {
+        let root = find(&mut set, v);
+        let component = components.entry(root).or_insert_with(|| {
+            order.push(root);
+            Component {
+                size: 0,
+                roots: Vec::new(),
+            }
+        });
+        component.size += 1;
+        if ps.is_empty() {
+            component.roots.push(name(&v));
+        }
+    }
+    let mut components: Vec<Component> = order
+        .iter()
+        .map(|r| components.remove(r).unwrap())
+        .collect();
+    components.sort_by_key(|c| std::cmp::Reverse(c.size));
+    Report {
+        roots,
+        duplicate_parents,
+        cycles,
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(text: &str) -> Vec<(&str, &str)> {
+        text.split_whitespace()
+            .map(|e| e.split_once(')').unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_tree() {
+        let report = validate(edges("COM)B B)C B)D D)Earth"));
+        assert!(report.is_tree());
+        assert_eq!(report.roots, vec!["COM"]);
+        assert_eq!(
+            report.to_string(),
+            "roots: COM\ncomponents: 1\nduplicate parents: 0\ncycles: 0"
+        );
+    }
+
+    #[test]
+    fn test_problems() {
+        let report = validate(edges("COM)B B)C X)C P)Q Q)R R)P R)S Z)Z A1)A2"));
+        assert!(!report.is_forest());
+        assert_eq!(report.roots, vec!["COM", "X", "A1"]);
+        assert_eq!(
+            report.duplicate_parents,
+            vec![DuplicateParent {
+                child: "C".to_string(),
+                parents: vec!["B".to_string(), "X".to_string()]
+            }]
+        );
+        assert_eq!(report.cycles, vec![vec!["P", "R", "Q"], vec!["Z"]]);
+        let sizes: Vec<usize> = report.components.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, vec![4, 4, 2, 1]);
+        assert_eq!(report.components[1].roots, Vec::<String>::new());
+        assert_eq!(
+            report.to_string(),
+            "roots: COM, X, A1\ncomponents: 4\n  4 nodes, roots: COM, X\n  4 nodes, roots: none\n  \
+             2 nodes, roots: A1\n  1 nodes, roots: none\nduplicate parents: 1\n  C: B, X\n\
+             cycles: 2\n  P -> R -> Q\n  Z"
+        );
+    }
+
+    #[test]
+    fn test_forest() {
+        let report = validate(edges("A)B C)D"));
+        assert!(report.is_forest());
+        assert!(!report.is_tree());
+        assert!(validate(Vec::new()).is_tree());
+    }
+}
@@ -1,6 +1,10 @@
-use std::cmp::{max, min};
+use std::cmp::min;
 use std::collections::HashMap;
 
+pub mod segments;
+pub mod svg;
+pub mod wire_set;
+
 #[derive(Debug)]
 pub struct Cursor {
     x: i32,
@@ -19,16 +23,10 @@ pub struct Cursor {
 
     // When checking for collisions, this gets updated with least steps to get a collision.
     least_steps_for_collision: i32,
-
-    // Bounding box.
-    min_x: i32,
-    min_y: i32,
-    max_x: i32,
-    max_y: i32,
 }
 
 /// Direction of a path instruction.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Dir {
     R,
     L,
@@ -37,11 +35,13 @@ enum Dir {
 }
 
 /// Describes one instruction in the path, e.g., "R995".
+#[derive(Debug, Clone, PartialEq)]
 struct PathInstruction {
     dir: Dir,
     length: i32,
 }
 
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Path(Vec<PathInstruction>);
 
 impl Cursor {
@@ -51,12 +51,8 @@ impl Cursor {
             y: 0,
             seen: HashMap::new(),
             steps: 0,
-            smol_manh_distance_collision: std::i32::MAX,
-            least_steps_for_collision: std::i32::MAX,
-            min_x: 0,
-            min_y: 0,
-            max_x: 0,
-            max_y: 0,
+            smol_manh_distance_collision: i32::MAX,
+            least_steps_for_collision: i32::MAX,
         }
     }
 
@@ -65,7 +61,7 @@ impl Cursor {
     }
 
     pub fn smol_manh_distance_collision(&self) -> Option<i32> {
-        if self.smol_manh_distance_collision != std::i32::MAX {
+        if self.smol_manh_distance_collision != i32::MAX {
             Some(self.smol_manh_distance_collision)
         } else {
             None
@@ -73,7 +69,7 @@ impl Cursor {
     }
 
     pub fn least_steps_for_collision(&self) -> Option<i32> {
-        if self.least_steps_for_collision != std::i32::MAX {
+        if self.least_steps_for_collision != i32::MAX {
             Some(self.least_steps_for_collision)
         } else {
             None
@@ -87,12 +83,7 @@ impl Cursor {
     }
 
     fn apply(&mut self, instr: &PathInstruction, check_collisions: bool) {
-        let inc = match instr.dir {
-            Dir::R => (1, 0),
-            Dir::L => (-1, 0),
-            Dir::U => (0, 1),
-            Dir::D => (0, -1),
-        };
+        let inc = instr.dir.inc();
         for _ in 0..instr.length {
             if check_collisions {
                 if let Some(previous_steps) = self.seen.get(&self.pos()) {
@@ -102,7 +93,6 @@ impl Cursor {
                         min(self.smol_manh_distance_collision, dist);
                     // Calculate steps for part 2.
                     let steps = previous_steps + self.steps;
-                    dbg!(steps);
                     self.least_steps_for_collision = min(self.least_steps_for_collision, steps);
                 }
             } else {
@@ -118,10 +108,6 @@ impl Cursor {
             self.y += inc.1;
             self.steps += 1;
         }
-        self.min_x = min(self.min_x, self.x);
-        self.max_x = max(self.max_x, self.x);
-        self.min_y = min(self.min_y, self.y);
-        self.max_y = max(self.max_y, self.y);
     }
 
     pub fn go_string(&mut self, s: &str, check_collisions: bool) {
@@ -133,31 +119,257 @@ impl Cursor {
     }
 }
 
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dir {
+    /// Change in (x, y) for one step. Up is +y.
+    fn inc(self) -> (i32, i32) {
+        match self {
+            Dir::R => (1, 0),
+            Dir::L => (-1, 0),
+            Dir::U => (0, 1),
+            Dir::D => (0, -1),
+        }
+    }
+
+    fn opposite(self) -> Dir {
+        match self {
+            Dir::R => Dir::L,
+            Dir::L => Dir::R,
+            Dir::U => Dir::D,
+            Dir::D => Dir::U,
+        }
+    }
+
+    fn turn_cw(self) -> Dir {
+        match self {
+            Dir::U => Dir::R,
+            Dir::R => Dir::D,
+            Dir::D => Dir::L,
+            Dir::L => Dir::U,
+        }
+    }
+
+    fn letter(self) -> char {
+        match self {
+            Dir::R => 'R',
+            Dir::L => 'L',
+            Dir::U => 'U',
+            Dir::D => 'D',
+        }
+    }
+}
+
+/// Why a path token could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathErrorKind {
+    /// There was nothing between two commas.
+    Empty,
+    /// First character is not one of R, L, U, D.
+    InvalidDirection(char),
+    /// Length is missing or not a non-negative integer.
+    InvalidLength(String),
+}
+
+/// Error returned when parsing a path. Points at the offending token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathError {
+    /// Index of the token, counting from 0.
+    pub index: usize,
+    /// The token as it appeared in the input.
+    pub token: String,
+    pub kind: PathErrorKind,
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "token {} ({:?}): ", self.index, self.token)?;
+        match &self.kind {
+            PathErrorKind::Empty => write!(f, "empty instruction"),
+            PathErrorKind::InvalidDirection(c) => write!(f, "invalid direction {:?}", c),
+            PathErrorKind::InvalidLength(l) => write!(f, "invalid length {:?}", l),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
 impl PathInstruction {
-    /// Parses one "R995".
-    fn parse(s: &[u8]) -> PathInstruction {
-        let dir = match s[0] {
-            b'R' => Dir::R,
-            b'L' => Dir::L,
-            b'U' => Dir::U,
-            b'D' => Dir::D,
-            _ => panic!("invalid char: {}", s[0]),
+    /// Parses one "R995". Direction may be lowercase, and surrounding whitespace is ignored.
+    fn parse(s: &str) -> Result<PathInstruction, PathErrorKind> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        let dir = match chars.next() {
+            None => return Err(PathErrorKind::Empty),
+            Some('R') | Some('r') => Dir::R,
+            Some('L') | Some('l') => Dir::L,
+            Some('U') | Some('u') => Dir::U,
+            Some('D') | Some('d') => Dir::D,
+            Some(c) => return Err(PathErrorKind::InvalidDirection(c)),
         };
-        let length = std::str::from_utf8(&s[1..])
-            .unwrap()
+        let length_str = chars.as_str().trim_start();
+        if length_str.is_empty() || !length_str.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(PathErrorKind::InvalidLength(length_str.to_string()));
+        }
+        let length = length_str
             .parse::<i32>()
-            .unwrap();
-        PathInstruction { dir, length }
+            .map_err(|_| PathErrorKind::InvalidLength(length_str.to_string()))?;
+        Ok(PathInstruction { dir, length })
     }
 }
 
-pub fn from_string(s: &str) -> Path {
-    Path(
+impl Path {
+    /// Parses comma-separated instructions, e.g., "R8,U5,L5,D3".
+    pub fn parse(s: &str) -> Result<Path, PathError> {
         s.trim()
             .split(',')
-            .map(|x| PathInstruction::parse(x.as_bytes()))
-            .collect(),
-    )
+            .enumerate()
+            .map(|(index, token)| {
+                PathInstruction::parse(token).map_err(|kind| PathError {
+                    index,
+                    token: token.to_string(),
+                    kind,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Path)
+    }
+}
+
+impl std::str::FromStr for Path {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Path, PathError> {
+        Path::parse(s)
+    }
+}
+
+/// Operations returning new paths. None of them change the cells visited, other than moving
+/// them around as the operation describes.
+impl Path {
+    /// Path going back from where this one stops to the origin.
+    pub fn reversed(&self) -> Path {
+        Path(
+            self.0
+                .iter()
+                .rev()
+                .map(|instr| PathInstruction {
+                    dir: instr.dir.opposite(),
+                    length: instr.length,
+                })
+                .collect(),
+        )
+    }
+
+    /// Merges consecutive moves in the same direction and drops zero-length moves. Paths
+    /// visiting the same points in the same order simplify to the same path.
+    pub fn simplified(&self) -> Path {
+        let mut out: Vec<PathInstruction> = Vec::new();
+        for instr in self.0.iter().filter(|instr| instr.length > 0) {
+            match out.last_mut() {
+                Some(last) if last.dir == instr.dir => last.length += instr.length,
+                _ => out.push(instr.clone()),
+            }
+        }
+        Path(out)
+    }
+
+    /// This path followed by other, starting where this one stops.
+    pub fn concat(&self, other: &Path) -> Path {
+        Path(self.0.iter().chain(other.0.iter()).cloned().collect())
+    }
+
+    fn map_dirs<F: Fn(Dir) -> Dir>(&self, f: F) -> Path {
+        Path(
+            self.0
+                .iter()
+                .map(|instr| PathInstruction {
+                    dir: f(instr.dir),
+                    length: instr.length,
+                })
+                .collect(),
+        )
+    }
+
+    /// Rotated 90 degrees clockwise around the origin.
+    pub fn rotated_cw(&self) -> Path {
+        self.map_dirs(Dir::turn_cw)
+    }
+
+    /// Rotated 90 degrees counterclockwise around the origin.
+    pub fn rotated_ccw(&self) -> Path {
+        self.map_dirs(|d| d.turn_cw().turn_cw().turn_cw())
+    }
+
+    /// Rotated 180 degrees around the origin.
+    pub fn rotated_180(&self) -> Path {
+        self.map_dirs(Dir::opposite)
+    }
+
+    /// Mirrored across the y axis: left and right swap.
+    pub fn mirrored_x(&self) -> Path {
+        self.map_dirs(|d| match d {
+            Dir::R | Dir::L => d.opposite(),
+            _ => d,
+        })
+    }
+
+    /// Mirrored across the x axis: up and down swap.
+    pub fn mirrored_y(&self) -> Path {
+        self.map_dirs(|d| match d {
+            Dir::U | Dir::D => d.opposite(),
+            _ => d,
+        })
+    }
+
+    /// Total number of steps.
+    pub fn steps(&self) -> i32 {
+        self.0.iter().map(|instr| instr.length).sum()
+    }
+
+    /// Yields every point visited as (position, steps taken to get there), from the origin at
+    /// step 0 to where the path stops. Points visited more than once are yielded each time.
+    pub fn points(&self) -> impl Iterator<Item = ((i32, i32), i32)> + '_ {
+        let incs = self
+            .0
+            .iter()
+            .flat_map(|instr| std::iter::repeat_n(instr.dir.inc(), instr.length as usize));
+        std::iter::once(((0, 0), 0)).chain(incs.scan(((0, 0), 0), |state, inc| {
+            let ((x, y), steps) = *state;
+            *state = ((x + inc.0, y + inc.1), steps + 1);
+            Some(*state)
+        }))
+    }
+
+    /// Point where the path stops.
+    pub fn end(&self) -> (i32, i32) {
+        self.0.iter().fold((0, 0), |(x, y), instr| {
+            let inc = instr.dir.inc();
+            (x + inc.0 * instr.length, y + inc.1 * instr.length)
+        })
+    }
+}
+
+/// Formats the path the way it is parsed, e.g., "R8,U5,L5,D3".
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, instr) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}{}", instr.dir.letter(), instr.length)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a path, panicking if it is malformed. See `Path::parse`.
+pub fn from_string(s: &str) -> Path {
+    Path::parse(s).unwrap_or_else(|e| panic!("{}", e))
 }
 
 #[cfg(test)]
@@ -166,11 +378,45 @@ mod tests {
 
     #[test]
     fn test_parse_pathinstruction() {
-        let p = PathInstruction::parse("L123".as_bytes());
+        let p = PathInstruction::parse("L123").unwrap();
         assert_eq!(p.dir, Dir::L);
         assert_eq!(p.length, 123);
     }
 
+    #[test]
+    fn test_parse_lenient() {
+        let p = Path::parse(" r12 , U0,\td7\n").unwrap();
+        assert_eq!(p.0.len(), 3);
+        assert_eq!(p.0[0].dir, Dir::R);
+        assert_eq!(p.0[0].length, 12);
+        assert_eq!(p.0[1].length, 0);
+        assert_eq!(p.0[2].dir, Dir::D);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let check = |s: &str, index: usize, kind: PathErrorKind| {
+            let err = s.parse::<Path>().err().unwrap();
+            assert_eq!((err.index, err.kind), (index, kind), "{}", s);
+        };
+        check("R1,,U2", 1, PathErrorKind::Empty);
+        check("R1,U2,", 2, PathErrorKind::Empty);
+        check("", 0, PathErrorKind::Empty);
+        check("R1,X2", 1, PathErrorKind::InvalidDirection('X'));
+        check("R", 0, PathErrorKind::InvalidLength("".to_string()));
+        check("R1,U-2", 1, PathErrorKind::InvalidLength("-2".to_string()));
+        check("R1,U2x", 1, PathErrorKind::InvalidLength("2x".to_string()));
+        check(
+            "R99999999999",
+            0,
+            PathErrorKind::InvalidLength("99999999999".to_string()),
+        );
+        assert_eq!(
+            Path::parse("R1,ü3").err().unwrap().to_string(),
+            "token 1 (\"ü3\"): invalid direction 'ü'"
+        );
+    }
+
     #[test]
     fn test_from_string() {
         let p = from_string("L123,R444");
@@ -213,4 +459,77 @@ mod tests {
         c.go_string("U62,R66,U55,R34,D71,R55,D58,R83", true);
         assert_eq!(c.least_steps_for_collision().unwrap(), 610);
     }
+
+    #[test]
+    fn test_display() {
+        let p = from_string("r8, U5,L0,D3");
+        assert_eq!(p.to_string(), "R8,U5,L0,D3");
+        assert_eq!(from_string(&p.to_string()), p);
+    }
+
+    #[test]
+    fn test_reversed() {
+        let p = from_string("R8,U5,L5,D3");
+        let r = p.reversed();
+        assert_eq!(r.to_string(), "U3,R5,D5,L8");
+        assert_eq!(r.reversed(), p);
+
+        // Reversed path visits the same points backwards.
+        let end = p.end();
+        let mut forward: Vec<(i32, i32)> = p.points().map(|(pos, _)| pos).collect();
+        let backward: Vec<(i32, i32)> = r
+            .points()
+            .map(|(pos, _)| (pos.0 + end.0, pos.1 + end.1))
+            .collect();
+        forward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn test_simplified() {
+        let p = from_string("R2,R3,U0,U1,L0,U2,D1");
+        assert_eq!(p.simplified().to_string(), "R5,U3,D1");
+        assert_eq!(p.simplified().steps(), p.steps());
+        assert_eq!(
+            from_string("U0").simplified().concat(&from_string("R1")),
+            from_string("R1")
+        );
+    }
+
+    #[test]
+    fn test_concat() {
+        let p = from_string("R8,U5").concat(&from_string("L5,D3"));
+        assert_eq!(p, from_string("R8,U5,L5,D3"));
+        assert_eq!(p.end(), (3, 2));
+    }
+
+    #[test]
+    fn test_rotate_and_mirror() {
+        let p = from_string("R8,U5,L5,D3");
+        assert_eq!(p.rotated_cw().to_string(), "D8,R5,U5,L3");
+        assert_eq!(p.rotated_ccw().to_string(), "U8,L5,D5,R3");
+        assert_eq!(p.rotated_180().to_string(), "L8,D5,R5,U3");
+        assert_eq!(p.rotated_cw().rotated_cw(), p.rotated_180());
+        assert_eq!(p.rotated_cw().rotated_ccw(), p);
+        assert_eq!(p.mirrored_x().to_string(), "L8,U5,R5,D3");
+        assert_eq!(p.mirrored_y().to_string(), "R8,D5,L5,U3");
+        assert_eq!(p.mirrored_x().mirrored_y(), p.rotated_180());
+        assert_eq!(p.rotated_cw().end(), (2, -3));
+    }
+
+    #[test]
+    fn test_points() {
+        let p = from_string("R2,U0,U1,L1");
+        let points: Vec<((i32, i32), i32)> = p.points().collect();
+        assert_eq!(
+            points,
+            vec![
+                ((0, 0), 0),
+                ((1, 0), 1),
+                ((2, 0), 2),
+                ((2, 1), 3),
+                ((1, 1), 4)
+            ]
+        );
+    }
 }
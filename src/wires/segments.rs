@@ -0,0 +1,355 @@
+//! Segment-based wire engine.
+//!
+//! Instead of recording every visited cell like `Cursor` does, each wire is stored as a list of
+//! axis-aligned segments, and crossings are found with a sweep line over x. Memory use is
+//! proportional to the number of path instructions rather than the wire length.
+
+use super::Path;
+use std::cmp::{max, min};
+use std::collections::{BTreeSet, HashMap};
+
+/// Straight run of cells visited by a wire.
+///
+/// Follows the same cell model as `Cursor`: an instruction of length n covers the cell it starts
+/// on and the n - 1 cells after it. The cell a wire finally stops on is not part of any segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// First cell covered.
+    start: (i32, i32),
+    /// Last cell covered (inclusive).
+    end: (i32, i32),
+    /// Steps taken to reach `start`.
+    steps: i32,
+}
+
+/// Wire stored as a list of segments.
+#[derive(Debug, Clone, Default)]
+pub struct SegmentWire {
+    segments: Vec<Segment>,
+    /// Origin, followed by the point each instruction ends on.
+    corners: Vec<(i32, i32)>,
+}
+
+/// Cell where two wires cross, with the fewest steps each wire takes to get there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intersection {
+    pub pos: (i32, i32),
+    pub steps: (i32, i32),
+}
+
+impl Segment {
+    /// Single-cell segments count as horizontal.
+    fn is_horizontal(&self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    fn x_range(&self) -> (i32, i32) {
+        (self.start.0.min(self.end.0), self.start.0.max(self.end.0))
+    }
+
+    fn y_range(&self) -> (i32, i32) {
+        (self.start.1.min(self.end.1), self.start.1.max(self.end.1))
+    }
+
+    /// Steps needed to reach given cell, which must be on this segment.
+    fn steps_at(&self, pos: (i32, i32)) -> i32 {
+        self.steps + (pos.0 - self.start.0).abs() + (pos.1 - self.start.1).abs()
+    }
+}
+
+impl SegmentWire {
+    pub fn from_path(path: &Path) -> SegmentWire {
+        let mut segments = Vec::new();
+        let mut corners = vec![(0, 0)];
+        let mut pos = (0, 0);
+        let mut steps = 0;
+        for instr in path.0.iter() {
+            let inc = instr.dir.inc();
+            if instr.length > 0 {
+                let last = instr.length - 1;
+                segments.push(Segment {
+                    start: pos,
+                    end: (pos.0 + inc.0 * last, pos.1 + inc.1 * last),
+                    steps,
+                });
+            }
+            pos = (pos.0 + inc.0 * instr.length, pos.1 + inc.1 * instr.length);
+            steps += instr.length;
+            corners.push(pos);
+        }
+        SegmentWire { segments, corners }
+    }
+
+    pub fn from_string(s: &str) -> SegmentWire {
+        SegmentWire::from_path(&super::from_string(s))
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Points where the wire turns, from the origin to where it stops.
+    pub fn corners(&self) -> &[(i32, i32)] {
+        &self.corners
+    }
+
+    /// Returns ((min_x, min_y), (max_x, max_y)) of all points the wire visits.
+    pub fn bounding_box(&self) -> ((i32, i32), (i32, i32)) {
+        self.corners.iter().fold(((0, 0), (0, 0)), |(lo, hi), p| {
+            (
+                (min(lo.0, p.0), min(lo.1, p.1)),
+                (max(hi.0, p.0), max(hi.1, p.1)),
+            )
+        })
+    }
+
+    fn horizontal(&self) -> Vec<&Segment> {
+        self.segments.iter().filter(|s| s.is_horizontal()).collect()
+    }
+
+    fn vertical(&self) -> Vec<&Segment> {
+        self.segments
+            .iter()
+            .filter(|s| !s.is_horizontal())
+            .collect()
+    }
+}
+
+/// Sweeps a vertical line over x, keeping horizontal segments that span the current x in a set
+/// ordered by y. Each vertical segment then queries the set for the y range it covers.
+///
+/// Calls `found` with the crossing cell and the indexes of the horizontal and vertical segment.
+fn sweep_perpendicular<F>(horizontal: &[&Segment], vertical: &[&Segment], mut found: F)
+where
+    F: FnMut((i32, i32), usize, usize),
+{
+    // Events are (x, kind, segment index). At equal x, segments are added before the vertical
+    // segments are checked, and removed after.
+    const ADD: u8 = 0;
+    const CHECK: u8 = 1;
+    const REMOVE: u8 = 2;
+    let mut events = Vec::with_capacity(horizontal.len() * 2 + vertical.len());
+    for (i, h) in horizontal.iter().enumerate() {
+        let (min_x, max_x) = h.x_range();
+        events.push((min_x, ADD, i));
+        events.push((max_x, REMOVE, i));
+    }
+    for (i, v) in vertical.iter().enumerate() {
+        events.push((v.start.0, CHECK, i));
+    }
+    events.sort_unstable();
+
+    let mut active: BTreeSet<(i32, usize)> = BTreeSet::new();
+    for (x, kind, i) in events {
+        match kind {
+            ADD => {
+                active.insert((horizontal[i].start.1, i));
+            }
+            REMOVE => {
+                active.remove(&(horizontal[i].start.1, i));
+            }
+            _ => {
+                let (min_y, max_y) = vertical[i].y_range();
+                for &(y, h) in active.range((min_y, 0)..=(max_y, usize::MAX)) {
+                    found((x, y), h, i);
+                }
+            }
+        }
+    }
+}
+
+/// Finds cells shared by segments lying on the same line. `line` picks the coordinate the
+/// segments share, `span` the range they cover along it, and `cell` builds a position back.
+fn overlap_collinear<L, S, C, F>(
+    a: &[&Segment],
+    b: &[&Segment],
+    line: L,
+    span: S,
+    cell: C,
+    mut found: F,
+) where
+    L: Fn(&Segment) -> i32,
+    S: Fn(&Segment) -> (i32, i32),
+    C: Fn(i32, i32) -> (i32, i32),
+    F: FnMut((i32, i32), usize, usize),
+{
+    let mut by_line: HashMap<i32, Vec<usize>> = HashMap::new();
+    for (j, s) in b.iter().enumerate() {
+        by_line.entry(line(s)).or_default().push(j);
+    }
+    for (i, s) in a.iter().enumerate() {
+        let l = line(s);
+        let (lo, hi) = span(s);
+        for &j in by_line.get(&l).into_iter().flatten() {
+            let (other_lo, other_hi) = span(b[j]);
+            for k in lo.max(other_lo)..=hi.min(other_hi) {
+                found(cell(l, k), i, j);
+            }
+        }
+    }
+}
+
+/// Returns every cell (other than the origin) where the two wires cross, sorted by position.
+pub fn intersections(a: &SegmentWire, b: &SegmentWire) -> Vec<Intersection> {
+    let (a_h, a_v) = (a.horizontal(), a.vertical());
+    let (b_h, b_v) = (b.horizontal(), b.vertical());
+
+    // Fewest steps for each wire, per crossing cell.
+    let mut best: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+    let mut record = |pos: (i32, i32), sa: &Segment, sb: &Segment| {
+        if pos == (0, 0) {
+            return;
+        }
+        let steps = (sa.steps_at(pos), sb.steps_at(pos));
+        let entry = best.entry(pos).or_insert(steps);
+        entry.0 = entry.0.min(steps.0);
+        entry.1 = entry.1.min(steps.1);
+    };
+
+    sweep_perpendicular(&a_h, &b_v, |pos, i, j| record(pos, a_h[i], b_v[j]));
+    sweep_perpendicular(&b_h, &a_v, |pos, i, j| record(pos, a_v[j], b_h[i]));
+    overlap_collinear(
+        &a_h,
+        &b_h,
+        |s| s.start.1,
+        Segment::x_range,
+        |y, x| (x, y),
+        |pos, i, j| record(pos, a_h[i], b_h[j]),
+    );
+    overlap_collinear(
+        &a_v,
+        &b_v,
+        |s| s.start.0,
+        Segment::y_range,
+        |x, y| (x, y),
+        |pos, i, j| record(pos, a_v[i], b_v[j]),
+    );
+
+    let mut out: Vec<Intersection> = best
+        .into_iter()
+        .map(|(pos, steps)| Intersection { pos, steps })
+        .collect();
+    out.sort_by_key(|i| i.pos);
+    out
+}
+
+/// Smallest Manhattan distance from the origin to a crossing. Same answer as
+/// `Cursor::smol_manh_distance_collision`.
+pub fn smol_manh_distance_collision(a: &SegmentWire, b: &SegmentWire) -> Option<i32> {
+    intersections(a, b)
+        .iter()
+        .map(|i| i.pos.0.abs() + i.pos.1.abs())
+        .min()
+}
+
+/// Fewest combined steps to a crossing. Same answer as `Cursor::least_steps_for_collision`.
+pub fn least_steps_for_collision(a: &SegmentWire, b: &SegmentWire) -> Option<i32> {
+    intersections(a, b)
+        .iter()
+        .map(|i| i.steps.0 + i.steps.1)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Cursor;
+    use super::*;
+    use std::fs;
+
+    const EXAMPLES: &[(&str, &str)] = &[
+        ("R8,U5,L5,D3", "U7,R6,D4,L4"),
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+            "U62,R66,U55,R34,D71,R55,D58,R83",
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+            "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+        ),
+        // Wires running on top of each other, in both directions.
+        ("R10,U2", "U1,R3,D1,R9"),
+        ("U5,R2,D8", "R2,U7,L1"),
+        // Single-cell moves.
+        ("R1,U1,R1,U1", "U1,R1,U1,R1"),
+    ];
+
+    fn check_same_as_cursor(first: &str, second: &str) {
+        let mut c = Cursor::new();
+        c.go_string(first, false);
+        c.go_string(second, true);
+        let a = SegmentWire::from_string(first);
+        let b = SegmentWire::from_string(second);
+        assert_eq!(
+            smol_manh_distance_collision(&a, &b),
+            c.smol_manh_distance_collision(),
+            "{} / {}",
+            first,
+            second
+        );
+        assert_eq!(
+            least_steps_for_collision(&a, &b),
+            c.least_steps_for_collision(),
+            "{} / {}",
+            first,
+            second
+        );
+    }
+
+    #[test]
+    fn test_from_path() {
+        let w = SegmentWire::from_string("R3,U0,U2,L1");
+        assert_eq!(
+            w.segments(),
+            &[
+                Segment {
+                    start: (0, 0),
+                    end: (2, 0),
+                    steps: 0
+                },
+                Segment {
+                    start: (3, 0),
+                    end: (3, 1),
+                    steps: 3
+                },
+                Segment {
+                    start: (3, 2),
+                    end: (3, 2),
+                    steps: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intersections() {
+        let a = SegmentWire::from_string("R8,U5,L5,D3");
+        let b = SegmentWire::from_string("U7,R6,D4,L4");
+        assert_eq!(
+            intersections(&a, &b),
+            vec![
+                Intersection {
+                    pos: (3, 3),
+                    steps: (20, 20)
+                },
+                Intersection {
+                    pos: (6, 5),
+                    steps: (15, 15)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_examples_match_cursor() {
+        for (first, second) in EXAMPLES {
+            check_same_as_cursor(first, second);
+        }
+    }
+
+    #[test]
+    fn test_input_matches_cursor() {
+        let contents = fs::read_to_string("inputs/day3_1.txt").unwrap();
+        let mut lines = contents.lines();
+        check_same_as_cursor(lines.next().unwrap(), lines.next().unwrap());
+    }
+}
@@ -0,0 +1,169 @@
+//! Renders a `WireSet` to SVG.
+//!
+//! Each wire is drawn as a polyline in its own colour. The origin and all crossings are marked,
+//! and the crossing closest to the origin and the one with the fewest combined steps are
+//! highlighted.
+
+use super::wire_set::{Crossing, WireSet};
+use std::fmt::Write;
+
+/// Colours for wires, reused if there are more wires than colours.
+const PALETTE: &[&str] = &[
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#e377c2", "#17becf", "#bcbd22",
+];
+
+const CLOSEST_COLOR: &str = "#d62728";
+const FEWEST_STEPS_COLOR: &str = "#000000";
+
+/// Size of the longer side of the picture, in pixels.
+const PICTURE_SIZE: f64 = 800.0;
+
+/// Maps wire coordinates to SVG coordinates. SVG y grows downwards, wire y grows upwards.
+struct Transform {
+    min_x: i32,
+    max_y: i32,
+    margin: i32,
+    /// Line width, and the unit marker sizes are based on.
+    unit: f64,
+}
+
+impl Transform {
+    fn x(&self, x: i32) -> i32 {
+        x - self.min_x + self.margin
+    }
+
+    fn y(&self, y: i32) -> i32 {
+        self.max_y - y + self.margin
+    }
+}
+
+fn circle(out: &mut String, t: &Transform, pos: (i32, i32), r: f64, style: &str, title: &str) {
+    writeln!(
+        out,
+        r#"  <circle cx="{}" cy="{}" r="{}" {}><title>{}</title></circle>"#,
+        t.x(pos.0),
+        t.y(pos.1),
+        r * t.unit,
+        style,
+        title
+    )
+    .unwrap();
+}
+
+fn describe(c: &Crossing) -> String {
+    let steps: Vec<String> = c
+        .steps
+        .iter()
+        .map(|(wire, steps)| format!("wire {}: {} steps", wire, steps))
+        .collect();
+    format!(
+        "({}, {}) distance {}, {}",
+        c.pos.0,
+        c.pos.1,
+        c.distance(),
+        steps.join(", ")
+    )
+}
+
+/// Returns an SVG document showing all wires in the set.
+pub fn render(set: &WireSet) -> String {
+    let ((min_x, min_y), (max_x, max_y)) = set.bounding_box();
+    let size = (max_x - min_x).max(max_y - min_y).max(1);
+    let t = Transform {
+        min_x,
+        max_y,
+        margin: (size / 20).max(1),
+        unit: f64::from(size) / 400.0,
+    };
+    let width = max_x - min_x + 2 * t.margin;
+    let height = max_y - min_y + 2 * t.margin;
+    let scale = PICTURE_SIZE / f64::from(width.max(height));
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="0 0 {} {}">"#,
+        f64::from(width) * scale,
+        f64::from(height) * scale,
+        width,
+        height
+    )
+    .unwrap();
+    writeln!(out, r#"  <rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+    for (i, wire) in set.wires().iter().enumerate() {
+        let points: Vec<String> = wire
+            .corners()
+            .iter()
+            .map(|p| format!("{},{}", t.x(p.0), t.y(p.1)))
+            .collect();
+        writeln!(
+            out,
+            r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"><title>wire {}</title></polyline>"#,
+            points.join(" "),
+            PALETTE[i % PALETTE.len()],
+            t.unit,
+            i
+        )
+        .unwrap();
+    }
+
+    let crossings = set.crossings();
+    for c in crossings.iter() {
+        circle(
+            &mut out,
+            &t,
+            c.pos,
+            2.0,
+            r##"fill="#ffffff" stroke="#555555" stroke-width="0.5""##,
+            &describe(c),
+        );
+    }
+
+    let closest = crossings.iter().min_by_key(|c| c.distance());
+    let fewest_steps = crossings.iter().min_by_key(|c| c.combined_steps());
+    if let Some(c) = closest {
+        let style = format!(
+            r#"fill="none" stroke="{}" stroke-width="{}""#,
+            CLOSEST_COLOR, t.unit
+        );
+        let title = format!("closest: {}", describe(c));
+        circle(&mut out, &t, c.pos, 6.0, &style, &title);
+    }
+    if let Some(c) = fewest_steps {
+        let style = format!(
+            r#"fill="none" stroke="{}" stroke-width="{}" stroke-dasharray="{}""#,
+            FEWEST_STEPS_COLOR,
+            t.unit,
+            2.0 * t.unit
+        );
+        let title = format!("fewest steps ({}): {}", c.combined_steps(), describe(c));
+        circle(&mut out, &t, c.pos, 9.0, &style, &title);
+    }
+
+    circle(&mut out, &t, (0, 0), 3.0, r#"fill="black""#, "origin");
+    writeln!(out, "</svg>").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut set = WireSet::new();
+        set.add_string("R8,U5,L5,D3");
+        set.add_string("U7,R6,D4,L4");
+        let svg = render(&set);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        // Two crossings, plus the closest and fewest steps highlights, plus the origin.
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(svg.contains("<title>closest: (3, 3) distance 6"));
+        assert!(svg.contains("<title>fewest steps (30): (6, 5)"));
+        // Origin is at the bottom left, y is flipped.
+        assert!(svg.contains(r#"points="1,8 9,8 9,3 4,3 4,6""#));
+    }
+}
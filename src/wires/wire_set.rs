@@ -0,0 +1,206 @@
+//! Any number of wires, and every point where they cross.
+
+use super::segments::{self, SegmentWire};
+use super::Path;
+use std::collections::BTreeMap;
+
+/// Collection of wires, all starting at the origin.
+#[derive(Debug, Clone, Default)]
+pub struct WireSet {
+    wires: Vec<SegmentWire>,
+}
+
+/// Cell where two or more wires meet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crossing {
+    pub pos: (i32, i32),
+    /// (wire index, fewest steps that wire takes to get here), sorted by wire index.
+    pub steps: Vec<(usize, i32)>,
+}
+
+impl Crossing {
+    /// Indexes of the wires meeting here.
+    pub fn wires(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps.iter().map(|(wire, _)| *wire)
+    }
+
+    /// Steps the given wire takes to get here, if it passes through.
+    pub fn steps_for(&self, wire: usize) -> Option<i32> {
+        self.steps
+            .iter()
+            .find(|(w, _)| *w == wire)
+            .map(|(_, steps)| *steps)
+    }
+
+    /// Manhattan distance from the origin.
+    pub fn distance(&self) -> i32 {
+        self.pos.0.abs() + self.pos.1.abs()
+    }
+
+    /// Sum of steps of all wires meeting here.
+    pub fn combined_steps(&self) -> i32 {
+        self.steps.iter().map(|(_, steps)| steps).sum()
+    }
+}
+
+impl WireSet {
+    pub fn new() -> WireSet {
+        Default::default()
+    }
+
+    /// Adds a wire, returns its index.
+    pub fn add_path(&mut self, path: &Path) -> usize {
+        self.wires.push(SegmentWire::from_path(path));
+        self.wires.len() - 1
+    }
+
+    /// Parses and adds a wire, e.g., "R8,U5,L5,D3". Returns its index.
+    pub fn add_string(&mut self, s: &str) -> usize {
+        self.add_path(&super::from_string(s))
+    }
+
+    pub fn len(&self) -> usize {
+        self.wires.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wires.is_empty()
+    }
+
+    pub fn wires(&self) -> &[SegmentWire] {
+        &self.wires
+    }
+
+    /// Returns ((min_x, min_y), (max_x, max_y)) covering all wires and the origin.
+    pub fn bounding_box(&self) -> ((i32, i32), (i32, i32)) {
+        self.wires.iter().map(SegmentWire::bounding_box).fold(
+            ((0, 0), (0, 0)),
+            |(lo, hi), (wlo, whi)| {
+                (
+                    (lo.0.min(wlo.0), lo.1.min(wlo.1)),
+                    (hi.0.max(whi.0), hi.1.max(whi.1)),
+                )
+            },
+        )
+    }
+
+    /// Returns every crossing between different wires, sorted by position. A wire crossing itself
+    /// does not count.
+    pub fn crossings(&self) -> Vec<Crossing> {
+        let mut found: BTreeMap<(i32, i32), BTreeMap<usize, i32>> = BTreeMap::new();
+        for a in 0..self.wires.len() {
+            for b in (a + 1)..self.wires.len() {
+                for i in segments::intersections(&self.wires[a], &self.wires[b]) {
+                    let wires = found.entry(i.pos).or_default();
+                    wires.insert(a, i.steps.0);
+                    wires.insert(b, i.steps.1);
+                }
+            }
+        }
+        found
+            .into_iter()
+            .map(|(pos, steps)| Crossing {
+                pos,
+                steps: steps.into_iter().collect(),
+            })
+            .collect()
+    }
+
+    /// Where wires `a` and `b` cross. A wire doesn't cross itself, so this is empty if they're
+    /// the same. Panics if either index is out of range.
+    fn intersections(&self, a: usize, b: usize) -> Vec<segments::Intersection> {
+        let (wire_a, wire_b) = (&self.wires[a], &self.wires[b]);
+        if a == b {
+            return Vec::new();
+        }
+        segments::intersections(wire_a, wire_b)
+    }
+
+    /// Crossings where two different wires meet, with the steps of just those two. Empty if `a`
+    /// and `b` are the same wire; panics if either index is out of range.
+    pub fn crossings_between(&self, a: usize, b: usize) -> Vec<Crossing> {
+        self.intersections(a, b)
+            .into_iter()
+            .map(|i| {
+                let mut steps = vec![(a, i.steps.0), (b, i.steps.1)];
+                steps.sort();
+                Crossing { pos: i.pos, steps }
+            })
+            .collect()
+    }
+
+    /// Crossing closest to the origin by Manhattan distance.
+    pub fn closest_crossing(&self) -> Option<Crossing> {
+        self.crossings().into_iter().min_by_key(Crossing::distance)
+    }
+
+    /// Crossing with the fewest steps, summed over all wires meeting there.
+    pub fn fewest_steps_crossing(&self) -> Option<Crossing> {
+        self.crossings()
+            .into_iter()
+            .min_by_key(Crossing::combined_steps)
+    }
+
+    /// Fewest steps the two given wires need, summed, to meet each other. None if they never
+    /// meet or are the same wire; panics if either index is out of range.
+    pub fn least_steps_between(&self, a: usize, b: usize) -> Option<i32> {
+        self.intersections(a, b)
+            .iter()
+            .map(|i| i.steps.0 + i.steps.1)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_wires() {
+        let mut set = WireSet::new();
+        set.add_string("R75,D30,R83,U83,L12,D49,R71,U7,L72");
+        set.add_string("U62,R66,U55,R34,D71,R55,D58,R83");
+        assert_eq!(set.closest_crossing().unwrap().distance(), 159);
+        assert_eq!(set.fewest_steps_crossing().unwrap().combined_steps(), 610);
+    }
+
+    #[test]
+    fn test_three_wires() {
+        let mut set = WireSet::new();
+        let a = set.add_string("R8,U5,L5,D3");
+        let b = set.add_string("U7,R6,D4,L4");
+        // Runs along the last leg of a, through the point where a and b cross.
+        let c = set.add_string("D1,R3,U8");
+        assert_eq!(set.len(), 3);
+
+        let crossings = set.crossings();
+        let positions: Vec<(i32, i32)> = crossings.iter().map(|c| c.pos).collect();
+        assert_eq!(positions, vec![(3, 0), (3, 3), (3, 4), (3, 5), (6, 5)]);
+        assert_eq!(crossings[0].steps, vec![(a, 3), (c, 5)]);
+        assert_eq!(crossings[1].steps, vec![(a, 20), (b, 20), (c, 8)]);
+        assert_eq!(crossings[3].steps, vec![(a, 18), (c, 10)]);
+        assert_eq!(crossings[4].steps, vec![(a, 15), (b, 15)]);
+        assert_eq!(crossings[1].wires().collect::<Vec<_>>(), vec![a, b, c]);
+        assert_eq!(crossings[1].combined_steps(), 48);
+
+        assert_eq!(set.crossings_between(a, b).len(), 2);
+        assert_eq!(set.crossings_between(a, c).len(), 4);
+        assert_eq!(set.crossings_between(b, c).len(), 1);
+        // Only the two wires asked about, whichever order they're given in.
+        assert_eq!(set.crossings_between(b, a)[0].steps, vec![(a, 20), (b, 20)]);
+        assert_eq!(set.least_steps_between(a, b), Some(30));
+        assert_eq!(set.least_steps_between(b, c), Some(28));
+        assert!(set.crossings_between(a, a).is_empty());
+        assert_eq!(set.least_steps_between(b, b), None);
+        assert_eq!(set.closest_crossing().unwrap().pos, (3, 0));
+        assert_eq!(set.fewest_steps_crossing().unwrap().pos, (3, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_between_out_of_range() {
+        let mut set = WireSet::new();
+        let a = set.add_string("R8,U5,L5,D3");
+        set.crossings_between(a, a + 1);
+    }
+}